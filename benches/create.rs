@@ -4,6 +4,7 @@ use criterion::{black_box, criterion_group, Criterion};
 
 use beef::lean::Cow as BeefCow;
 use dairy::Cow as DairyCow;
+use dairy::SmallCow as DairySmallCow;
 use std::borrow::Cow as StdCow;
 
 const NTH_WORD: usize = 2;
@@ -81,6 +82,24 @@ fn create_str_mixed(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("create_mixed/str/dairy_small", |b| {
+        b.iter(|| {
+            let cows: Vec<DairySmallCow> = words
+                .iter()
+                .copied()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i % NTH_WORD == 0 {
+                        DairySmallCow::copied(word)
+                    } else {
+                        DairySmallCow::borrowed(word)
+                    }
+                })
+                .collect();
+            black_box(cows);
+        })
+    });
+
     c.bench_function("create_mixed/str/td", |b| {
         b.iter(|| {
             let cows: Vec<StdCow<str>> = words
@@ -0,0 +1,32 @@
+use std::ffi::{CStr, CString};
+
+use criterion::{black_box, criterion_group, Criterion};
+
+use dairy::Cow as DairyCow;
+use std::borrow::Cow as StdCow;
+
+static TEXT: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit. Nam ut ipsum quis orci sagittis consectetur in vel nisi. Fusce eu magna condimentum turpis posuere vulputate id eu massa.\0";
+
+fn deref_c_str(c: &mut Criterion) {
+    let c_str = CStr::from_bytes_with_nul(TEXT).unwrap();
+
+    c.bench_function("deref/c_str/dairy/borrowed", |b| {
+        let cow = DairyCow::borrowed(c_str);
+        b.iter(|| black_box(&cow).to_bytes().len())
+    });
+    c.bench_function("deref/c_str/std/borrowed", |b| {
+        let cow = StdCow::Borrowed(c_str);
+        b.iter(|| black_box(&cow).to_bytes().len())
+    });
+
+    c.bench_function("deref/c_str/dairy/owned", |b| {
+        let cow: DairyCow<CStr> = DairyCow::owned(CString::from(c_str));
+        b.iter(|| black_box(&cow).to_bytes().len())
+    });
+    c.bench_function("deref/c_str/std/owned", |b| {
+        let cow: StdCow<CStr> = StdCow::Owned(CString::from(c_str));
+        b.iter(|| black_box(&cow).to_bytes().len())
+    });
+}
+
+criterion_group!(benches, deref_c_str);
@@ -1,6 +1,7 @@
 mod create;
+mod deref;
 mod maybe;
 
 use criterion::criterion_main;
 
-criterion_main!(create::benches, maybe::benches);
+criterion_main!(create::benches, deref::benches, maybe::benches);
@@ -15,9 +15,10 @@ fn beef_cow_to_beef_cow(input: BeefCow<str>) -> BeefCow<str> {
 }
 
 fn dairy_cow_to_dairy_cow(mut input: DairyCow<str>) -> DairyCow<str> {
-    if !input.starts_with("https://") {
-        input.apply(|o| o.insert_str(0, "https://"));
-    }
+    input.modify_if(
+        |s| !s.starts_with("https://"),
+        |o| o.insert_str(0, "https://"),
+    );
     input
 }
 
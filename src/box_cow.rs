@@ -0,0 +1,349 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::boxed::Box;
+
+#[cfg(feature = "std")]
+use std::{
+    ffi::{CStr, OsStr},
+    path::Path,
+};
+
+use crate::imp::{BoxCow as _, DairyBox};
+use crate::Cow;
+
+/// A clone-on-write smart pointer whose owned data is a [`Box`].
+///
+/// This is useful for data that is already stored in a box, for example
+/// `Box<str>` or `Box<[T]>`. Because a box has no spare capacity only the
+/// length and the ownership tag need to be stored so `BoxCow` is always two
+/// words wide and [`.into_boxed()`][BoxCow::into_boxed] never clones owned
+/// data.
+///
+/// On 64-bit platforms the ownership tag leaves the same niches as
+/// [`Cow`][crate::Cow], so `Option<Option<BoxCow<T>>>` is the same size as
+/// `BoxCow<T>`. On 32-bit platforms the only niche is the non-null pointer.
+///
+/// # Examples
+///
+/// ```
+/// use dairy::BoxCow;
+///
+/// let cow: BoxCow<str> = BoxCow::owned(Box::from("moo"));
+/// assert!(cow.is_owned());
+/// assert_eq!(cow.into_boxed(), Box::from("moo"));
+/// ```
+pub struct BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    inner: T::BoxCow,
+
+    /// For the lifetime, this makes `BoxCow` covariant in it.
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    /// Construct from borrowed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::BoxCow;
+    /// let cow: BoxCow<str> = BoxCow::borrowed("moo");
+    /// ```
+    #[inline]
+    pub fn borrowed(b: &'a T) -> Self {
+        Self {
+            inner: T::BoxCow::borrowed(b),
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct from boxed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::BoxCow;
+    /// let cow: BoxCow<str> = BoxCow::owned(Box::from("moo"));
+    /// ```
+    #[inline]
+    pub fn owned(o: Box<T>) -> Self {
+        Self {
+            inner: T::BoxCow::owned(o),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns true if the data is borrowed.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        self.inner.is_borrowed()
+    }
+
+    /// Returns true if the data is owned.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        self.inner.is_owned()
+    }
+
+    #[inline]
+    fn make_ref(&self) -> &T {
+        self.inner.make_ref()
+    }
+
+    /// Returns the borrowed data if the data is borrowed.
+    ///
+    /// See [`Cow::as_borrowed`] for more details.
+    #[inline]
+    pub fn as_borrowed(&self) -> Option<&'a T> {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        unsafe { self.inner.as_borrowed() }
+    }
+
+    /// Converts into owned data.
+    ///
+    /// Clones the data if it is not already owned.
+    #[inline]
+    pub fn into_owned(self) -> T::Owned
+    where
+        T: 'a,
+        T::Owned: From<Box<T>>,
+    {
+        match self.as_borrowed() {
+            Some(b) => b.to_owned(),
+            None => T::Owned::from(self.into_boxed()),
+        }
+    }
+
+    /// Converts into boxed data.
+    ///
+    /// Clones the data if it is not already owned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::BoxCow;
+    ///
+    /// let b: Box<str> = Box::from("moo");
+    /// let ptr = b.as_ptr();
+    /// let cow = BoxCow::owned(b);
+    /// let b = cow.into_boxed();
+    /// assert_eq!(b.as_ptr(), ptr);
+    /// ```
+    #[inline]
+    pub fn into_boxed(self) -> Box<T> {
+        self.inner.into_boxed()
+    }
+}
+
+impl<'a, T> Deref for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.make_ref()
+    }
+}
+
+impl<'a, T> Borrow<T> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.make_ref()
+    }
+}
+
+impl<'a, T> AsRef<T> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.make_ref()
+    }
+}
+
+impl<'a, T> Clone for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'a, T> fmt::Display for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<'a, T> Hash for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<'a, 'b, T, U> PartialEq<BoxCow<'b, U>> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + PartialEq<U>,
+    U: ?Sized + DairyBox,
+{
+    #[inline]
+    fn eq(&self, other: &BoxCow<'b, U>) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl<'a, T> Eq for BoxCow<'a, T> where T: ?Sized + DairyBox + Eq {}
+
+impl<'a, T> PartialOrd for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T> Ord for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl<'a, T> PartialEq<T> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        PartialEq::eq(&**self, other)
+    }
+}
+
+impl<'a, T> PartialEq<&T> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &&T) -> bool {
+        PartialEq::eq(&**self, *other)
+    }
+}
+
+impl<'a, T> From<&'a T> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    #[inline]
+    fn from(b: &'a T) -> Self {
+        Self::borrowed(b)
+    }
+}
+
+impl<'a, T> From<Box<T>> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox,
+{
+    #[inline]
+    fn from(o: Box<T>) -> Self {
+        Self::owned(o)
+    }
+}
+
+impl<'a, T> From<Cow<'a, T>> for BoxCow<'a, T>
+where
+    T: ?Sized + DairyBox + 'a,
+{
+    #[inline]
+    fn from(c: Cow<'a, T>) -> Self {
+        match c.as_borrowed() {
+            Some(b) => Self::borrowed(b),
+            None => Self::owned(c.into_boxed()),
+        }
+    }
+}
+
+impl From<BoxCow<'_, str>> for Box<str> {
+    #[inline]
+    fn from(c: BoxCow<'_, str>) -> Self {
+        c.into_boxed()
+    }
+}
+
+impl<'a, T: 'a + Clone> From<BoxCow<'a, [T]>> for Box<[T]> {
+    #[inline]
+    fn from(c: BoxCow<'a, [T]>) -> Self {
+        c.into_boxed()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<BoxCow<'_, CStr>> for Box<CStr> {
+    #[inline]
+    fn from(c: BoxCow<'_, CStr>) -> Self {
+        c.into_boxed()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<BoxCow<'_, OsStr>> for Box<OsStr> {
+    #[inline]
+    fn from(c: BoxCow<'_, OsStr>) -> Self {
+        c.into_boxed()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<BoxCow<'_, Path>> for Box<Path> {
+    #[inline]
+    fn from(c: BoxCow<'_, Path>) -> Self {
+        c.into_boxed()
+    }
+}
+
+unsafe impl<'a, T> Send for BoxCow<'a, T> where T: ?Sized + DairyBox + Send + Sync {}
+
+unsafe impl<'a, T> Sync for BoxCow<'a, T> where T: ?Sized + DairyBox + Sync {}
@@ -0,0 +1,137 @@
+//! Constant constructors for `Cow`.
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+use std::ffi::CStr;
+
+use crate::imp::{CompactCow, Extent};
+use crate::Cow;
+
+impl<'a> Cow<'a, str> {
+    /// Construct from borrowed data in a constant context.
+    ///
+    /// This is the same as [`Cow::borrowed`] but can be used to initialize a
+    /// `static` or `const`. See also the [`cow!`][crate::cow] macro.
+    ///
+    /// # Panics
+    ///
+    /// If the length does not fit in 61 bits, which is only possible on 64-bit
+    /// platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// static NAME: Cow<str> = Cow::const_str("moo");
+    /// assert!(NAME.is_borrowed());
+    /// assert_eq!(NAME, "moo");
+    /// ```
+    #[inline]
+    pub const fn const_str(b: &'a str) -> Self {
+        // SAFETY: The pointer is from a valid reference.
+        let parts = unsafe { Extent::borrowed(b.as_ptr(), b.len()) };
+        Self {
+            // SAFETY: The parts were returned by `Extent::borrowed`.
+            inner: unsafe { CompactCow::from_borrowed_parts(parts) },
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Cow<'a, [T]>
+where
+    T: Clone,
+{
+    /// Construct from borrowed data in a constant context.
+    ///
+    /// This is the same as [`Cow::borrowed`] but can be used to initialize a
+    /// `static` or `const`.
+    ///
+    /// # Panics
+    ///
+    /// See [`Cow::const_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// static PRIMES: Cow<[u32]> = Cow::const_slice(&[2, 3, 5, 7]);
+    /// assert!(PRIMES.is_borrowed());
+    /// assert_eq!(PRIMES[3], 7);
+    /// ```
+    #[inline]
+    pub const fn const_slice(b: &'a [T]) -> Self {
+        // SAFETY: The pointer is from a valid reference.
+        let parts = unsafe { Extent::borrowed(b.as_ptr(), b.len()) };
+        Self {
+            // SAFETY: The parts were returned by `Extent::borrowed`.
+            inner: unsafe { CompactCow::from_borrowed_parts(parts) },
+            marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Cow<'a, CStr> {
+    /// Construct from borrowed data in a constant context.
+    ///
+    /// This is the same as [`Cow::borrowed`] but can be used to initialize a
+    /// `static` or `const`.
+    ///
+    /// # Panics
+    ///
+    /// See [`Cow::const_str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::CStr;
+    ///
+    /// use dairy::Cow;
+    ///
+    /// const MOO: &CStr = match CStr::from_bytes_with_nul(b"moo\0") {
+    ///     Ok(c) => c,
+    ///     Err(_) => panic!("invalid C string"),
+    /// };
+    ///
+    /// static NAME: Cow<CStr> = Cow::const_c_str(MOO);
+    /// assert!(NAME.is_borrowed());
+    /// assert_eq!(NAME.to_bytes(), b"moo");
+    /// ```
+    #[inline]
+    pub const fn const_c_str(b: &'a CStr) -> Self {
+        let bytes = b.to_bytes_with_nul();
+        // SAFETY: The pointer is from a valid reference.
+        let parts = unsafe { Extent::borrowed(bytes.as_ptr(), bytes.len()) };
+        Self {
+            // SAFETY: The parts were returned by `Extent::borrowed` and are
+            // the same as the ones returned by `CStr::unmake_borrowed`.
+            inner: unsafe { CompactCow::from_borrowed_parts(parts) },
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Constructs a borrowed [`Cow<str>`][crate::Cow] in a constant context.
+///
+/// This expands to a call to [`Cow::const_str`][crate::Cow::const_str], so it
+/// can be used to initialize a `static` or `const`.
+///
+/// # Examples
+///
+/// ```
+/// use dairy::Cow;
+///
+/// static DEFAULTS: [Cow<str>; 2] = [dairy::cow!("moo"), dairy::cow!("baa")];
+/// assert!(DEFAULTS[0].is_borrowed());
+/// assert_eq!(DEFAULTS[1], "baa");
+/// ```
+#[macro_export]
+macro_rules! cow {
+    ($s:expr) => {
+        $crate::Cow::const_str($s)
+    };
+}
@@ -0,0 +1,66 @@
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use crate::imp::Cow as _;
+use crate::{Cow, Dairy};
+
+/// A guard providing mutable access to the owned data of a [`Cow`].
+///
+/// This is returned by [`.to_mut()`][Cow::to_mut]. The owned data is written
+/// back to the `Cow` when the guard is dropped.
+pub struct CowMut<'c, T>
+where
+    T: ?Sized + Dairy + 'c,
+{
+    inner: <T::Cow as crate::imp::Cow<T>>::Mut<'c>,
+}
+
+impl<'c, T> CowMut<'c, T>
+where
+    T: ?Sized + Dairy + 'c,
+{
+    #[inline]
+    pub(crate) fn new(cow: &'c mut Cow<'_, T>) -> Self {
+        Self {
+            inner: cow.inner.to_mut(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_inner(inner: <T::Cow as crate::imp::Cow<T>>::Mut<'c>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'c, T> Deref for CowMut<'c, T>
+where
+    T: ?Sized + Dairy + 'c,
+{
+    type Target = T::Owned;
+
+    #[inline]
+    fn deref(&self) -> &T::Owned {
+        &self.inner
+    }
+}
+
+impl<'c, T> DerefMut for CowMut<'c, T>
+where
+    T: ?Sized + Dairy + 'c,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T::Owned {
+        &mut self.inner
+    }
+}
+
+impl<'c, T> fmt::Debug for CowMut<'c, T>
+where
+    T: ?Sized + Dairy + 'c,
+    T::Owned: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,40 @@
+use core::fmt;
+
+/// An error returned when owned data is too large to be stored in a
+/// [`Cow`][crate::Cow].
+///
+/// This is returned by [`Cow::try_owned`][crate::Cow::try_owned] and
+/// [`Cow::checked_apply`][crate::Cow::checked_apply] and contains the owned
+/// data so that it is not lost.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CapacityError<O> {
+    owned: O,
+}
+
+impl<O> CapacityError<O> {
+    #[inline]
+    pub(crate) fn new(owned: O) -> Self {
+        Self { owned }
+    }
+
+    /// Returns the owned data that could not be stored.
+    #[inline]
+    pub fn into_inner(self) -> O {
+        self.owned
+    }
+}
+
+impl<O> fmt::Debug for CapacityError<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacityError").finish_non_exhaustive()
+    }
+}
+
+impl<O> fmt::Display for CapacityError<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("capacity out of bounds")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<O> std::error::Error for CapacityError<O> {}
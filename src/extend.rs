@@ -91,7 +91,7 @@ impl<'a, 'b, T: 'a + 'b + Copy> Extend<&'b T> for Cow<'a, [T]> {
     }
 }
 
-impl<'a, 'b, T: 'a + Clone> Extend<T> for Cow<'a, [T]> {
+impl<'a, T: 'a + Clone> Extend<T> for Cow<'a, [T]> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         self.apply(move |o| o.extend(iter))
@@ -9,11 +9,12 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{Cow, Dairy};
+use crate::imp::Cow as _;
+use crate::{Cow, Dairy, Ownership};
 
 impl<'a, T> From<StdCow<'a, T>> for Cow<'a, T>
 where
-    T: Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     #[inline]
     fn from(c: StdCow<'a, T>) -> Self {
@@ -24,6 +25,30 @@ where
     }
 }
 
+impl<'a, T> From<Ownership<'a, T>> for Cow<'a, T>
+where
+    T: ?Sized + Dairy,
+{
+    #[inline]
+    fn from(o: Ownership<'a, T>) -> Self {
+        match o {
+            Ownership::Borrowed(b) => Self::borrowed(b),
+            Ownership::Owned(o) => Self::owned(o),
+        }
+    }
+}
+
+impl<'a, T> From<Cow<'a, T>> for StdCow<'a, T>
+where
+    T: ?Sized + Dairy,
+{
+    #[inline]
+    fn from(c: Cow<'a, T>) -> Self {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        unsafe { c.inner.into_std() }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Cow<str>
 ////////////////////////////////////////////////////////////////////////////////
@@ -85,7 +85,7 @@ impl<'a, 'b, T: 'a + 'b + Copy> FromIterator<&'b T> for Cow<'a, [T]> {
     }
 }
 
-impl<'a, 'b, T: 'a + Clone> FromIterator<T> for Cow<'a, [T]> {
+impl<'a, T: 'a + Clone> FromIterator<T> for Cow<'a, [T]> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Cow::owned(Vec::from_iter(iter))
@@ -0,0 +1,149 @@
+//! A compact `Cow` whose owned variant is a [`Box`].
+
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr::NonNull;
+
+use alloc::boxed::Box;
+
+use crate::imp;
+use crate::imp::BoxCow as _;
+use crate::ToBoxed;
+
+use super::convert::Convert;
+use super::extent::{BoxExtent, Extent};
+
+/// A compact clone-on-write smart pointer that owns a [`Box`].
+///
+/// Since a box has no capacity only the length and the ownership tag need to
+/// be stored.
+pub struct Cow<T>
+where
+    T: ?Sized + Convert<Extent = Extent>,
+{
+    /// Pointer to the data.
+    ptr: NonNull<T::Ptr>,
+
+    /// The length and the ownership tag.
+    extent: BoxExtent,
+
+    /// For drop check, owned data is dropped with the `Cow`.
+    marker: PhantomData<Box<T>>,
+}
+
+impl<T> Cow<T>
+where
+    T: ?Sized + Convert<Extent = Extent>,
+{
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads. If the data is owned it must have
+    /// been returned by [`Box::into_raw`].
+    #[inline]
+    unsafe fn from_ptr(ptr: *const T, owned: bool) -> Self {
+        let (ptr, extent) = unsafe { T::unmake_ptr(ptr) };
+        let extent = if owned {
+            BoxExtent::owned(extent.len())
+        } else {
+            BoxExtent::borrowed(extent.len())
+        };
+        Self {
+            ptr,
+            extent,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn make_ptr(&self) -> *const T {
+        // SAFETY: The pointer is non-null and the length is the same as the
+        // one that was stored.
+        unsafe {
+            let (ptr, extent) = Extent::borrowed(self.ptr.as_ptr(), self.extent.len());
+            T::make_ptr(ptr, extent)
+        }
+    }
+}
+
+impl<T> imp::BoxCow<T> for Cow<T>
+where
+    T: ?Sized + Convert<Extent = Extent> + ToBoxed,
+{
+    #[inline]
+    fn borrowed(b: &T) -> Self {
+        // SAFETY: A reference is always valid for reads.
+        unsafe { Self::from_ptr(b, false) }
+    }
+
+    #[inline]
+    fn owned(o: Box<T>) -> Self {
+        // SAFETY: The box is reconstructed from this pointer when dropped, the
+        // pointer is never turned into a reference first so that it keeps the
+        // provenance required by `Box::from_raw`.
+        unsafe { Self::from_ptr(Box::into_raw(o), true) }
+    }
+
+    #[inline]
+    fn is_borrowed(&self) -> bool {
+        !self.extent.is_owned()
+    }
+
+    #[inline]
+    fn is_owned(&self) -> bool {
+        self.extent.is_owned()
+    }
+
+    #[inline]
+    fn make_ref(&self) -> &T {
+        // SAFETY: This is valid for both owned and borrowed variants.
+        unsafe { &*self.make_ptr() }
+    }
+
+    #[inline]
+    fn into_boxed(self) -> Box<T> {
+        if self.is_owned() {
+            let cow = ManuallyDrop::new(self);
+            // SAFETY: The pointer was returned by `Box::into_raw`.
+            unsafe { Box::from_raw(cow.make_ptr() as *mut T) }
+        } else {
+            T::to_boxed(self.make_ref().to_owned())
+        }
+    }
+
+    #[inline]
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T> {
+        if self.is_borrowed() {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Some(unsafe { &*self.make_ptr() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Clone for Cow<T>
+where
+    T: ?Sized + Convert<Extent = Extent> + ToBoxed,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        if self.is_owned() {
+            imp::BoxCow::owned(T::to_boxed(self.make_ref().to_owned()))
+        } else {
+            Self { ..*self }
+        }
+    }
+}
+
+impl<T> Drop for Cow<T>
+where
+    T: ?Sized + Convert<Extent = Extent>,
+{
+    #[inline]
+    fn drop(&mut self) {
+        if self.extent.is_owned() {
+            // SAFETY: The pointer was returned by `Box::into_raw`.
+            unsafe { drop(Box::from_raw(self.make_ptr() as *mut T)) };
+        }
+    }
+}
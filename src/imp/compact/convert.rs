@@ -1,26 +1,66 @@
 //! Convert a [`ToOwned`] type to and from parts.
 
+use core::mem;
 use core::mem::ManuallyDrop;
 use core::ptr;
 use core::ptr::NonNull;
 
 use alloc::borrow::ToOwned;
 use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 
-#[cfg(all(feature = "std", unix))]
-use std::os::unix::ffi::{OsStrExt, OsStringExt};
-#[cfg(all(feature = "std", target_os = "wasi"))]
-use std::os::wasi::ffi::{OsStrExt, OsStringExt};
-
 use super::extent::Extent;
 
-/// Whether or not this extra data describes an owned type.
+/// The parts that a [`Convert`] type is converted to.
+pub type Parts<T> = (NonNull<<T as Convert>::Ptr>, <T as Convert>::Extent);
+
+/// Whether or not this extra data describes an owned or shared type.
+///
+/// This is implemented for [`Extent`], custom extents only need to implement it
+/// when not using [`Extent`] as the [`Convert::Extent`] type.
 pub trait IsOwned {
+    /// Returns true if the extra data describes owned data.
     fn is_owned(&self) -> bool;
+
+    /// Returns true if the extra data describes shared data.
+    fn is_shared(&self) -> bool;
+
+    /// Converts extra data describing a borrowed type into extra data
+    /// describing the same data but shared.
+    fn into_shared(self) -> Self;
 }
 
 /// Convert a [`ToOwned`] type to and from parts.
+///
+/// Implementing this trait allows a type to use the compact [`Cow`]
+/// implementation which stores a pointer and an [`Extent`] instead of the
+/// standard library `Cow`. See the [`repr`][crate::repr] module for an example.
+///
+/// # Safety
+///
+/// Implementations must guarantee the following.
+///
+/// - Parts returned by `unmake_ptr`, `try_unmake_owned`, and `unmake_shared`
+///   can be passed back to `make_ptr` to get a pointer to the original value.
+///   For parts returned by `unmake_ptr` the pointer must keep the provenance
+///   of the original pointer.
+/// - Parts returned by `try_unmake_owned` can be passed back to `make_owned`
+///   to reconstruct the original owned value. Ownership of the data is
+///   transferred to the parts.
+/// - The extent returned by `unmake_ptr` is not owned or shared, the
+///   extent returned by `try_unmake_owned` is owned, and the extent returned
+///   by `unmake_shared` is shared. The default extent must describe borrowed
+///   data.
+/// - The parts returned by `empty` describe valid borrowed data that lives
+///   forever. The provided implementation returns a dangling pointer and the
+///   default extent, so types that cannot be empty, like `CStr`, must override
+///   it.
+/// - The pointer returned by `make_ptr` for shared parts is the same as the
+///   one returned by [`Arc::into_raw`][alloc::sync::Arc::into_raw].
+///
+/// [`Cow`]: crate::Cow
 pub unsafe trait Convert: ToOwned {
     /// The pointer type that will be used in the better `Cow`.
     type Ptr;
@@ -29,30 +69,105 @@ pub unsafe trait Convert: ToOwned {
     /// variant of this type. For example: length and capacity.
     type Extent: Copy + Default + IsOwned;
 
+    /// Convert a pointer to a borrowed version of self into parts.
+    ///
+    /// Unlike [`unmake_borrowed`][Convert::unmake_borrowed] this keeps the
+    /// provenance of the pointer, so that the pointer returned by `make_ptr`
+    /// can be passed to [`Box::from_raw`][alloc::boxed::Box::from_raw] or
+    /// [`Arc::from_raw`][alloc::sync::Arc::from_raw].
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads.
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent);
+
     /// Convert a borrowed version of self into parts.
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent);
+    #[inline]
+    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        // SAFETY: A reference is always valid for reads.
+        unsafe { Self::unmake_ptr(b) }
+    }
+
+    /// Returns the parts of an empty borrowed version of self.
+    ///
+    /// A [`Cow`][crate::Cow] is left in this state while its owned data is
+    /// taken out, for example if the guard returned by
+    /// [`.to_mut()`][crate::Cow::to_mut] is leaked or the function given to
+    /// [`.apply()`][crate::Cow::apply] panics.
+    #[inline]
+    fn empty() -> (NonNull<Self::Ptr>, Self::Extent) {
+        (NonNull::dangling(), Self::Extent::default())
+    }
+
+    /// Convert an owned version of self into parts.
+    ///
+    /// Returns the owned version back if it cannot be represented as parts.
+    fn try_unmake_owned(o: Self::Owned) -> Result<Parts<Self>, Self::Owned>;
 
     /// Convert an owned version of self into parts.
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent);
+    ///
+    /// # Panics
+    ///
+    /// If the owned version cannot be represented as parts.
+    #[inline]
+    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
+        match Self::try_unmake_owned(o) {
+            Ok(parts) => parts,
+            Err(_) => panic!("capacity out of bounds"),
+        }
+    }
+
+    /// Convert an owned version of self into a shared version.
+    ///
+    /// Returns the owned version back if sharing is not supported, this is the
+    /// default. This is only available on targets with atomic pointers.
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Err(o)
+    }
+
+    /// Convert a shared version of self into parts.
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn unmake_shared(a: Arc<Self>) -> (NonNull<Self::Ptr>, Self::Extent) {
+        // SAFETY: The data stays alive until the parts are passed to
+        // `Arc::from_raw`.
+        let (ptr, extent) = unsafe { Self::unmake_ptr(Arc::into_raw(a)) };
+        (ptr, extent.into_shared())
+    }
 
     /// Returns a pointer to self constructed from parts.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have been returned by one of the `unmake_*` functions
+    /// and the data must still be alive.
     unsafe fn make_ptr(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> *const Self;
 
     /// Returns an owned version of self constructed from parts.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have been returned by `try_unmake_owned` and must not be
+    /// used again afterwards.
     unsafe fn make_owned(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> Self::Owned;
 }
 
-impl IsOwned for bool {
+impl IsOwned for Extent {
     #[inline]
     fn is_owned(&self) -> bool {
-        *self
+        Extent::is_owned(self)
     }
-}
 
-impl IsOwned for Extent {
     #[inline]
-    fn is_owned(&self) -> bool {
-        self.capacity() != 0
+    fn is_shared(&self) -> bool {
+        Extent::is_shared(self)
+    }
+
+    #[inline]
+    fn into_shared(self) -> Self {
+        Extent::into_shared(self)
     }
 }
 
@@ -61,14 +176,35 @@ unsafe impl Convert for str {
     type Extent = Extent;
 
     #[inline]
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
-        unsafe { Extent::borrowed(b.as_ptr(), b.len()) }
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        let ptr = ptr as *const [u8];
+        unsafe { Extent::borrowed(ptr as *const u8, ptr.len()) }
     }
 
     #[inline]
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
+    fn try_unmake_owned(mut o: Self::Owned) -> Result<Parts<Self>, Self::Owned> {
+        let cap = Extent::fit_capacity(o.len(), o.capacity());
+        if cap < o.capacity() {
+            o.shrink_to(cap);
+        }
+        if !Extent::can_pack(o.len(), o.capacity()) {
+            // Guarantees that the capacity is equal to the length.
+            o = o.into_boxed_str().into_string();
+        }
         let mut o = ManuallyDrop::new(o);
-        unsafe { Extent::owned(o.as_mut_ptr(), o.len(), o.capacity()) }
+        // The pointer is taken from the `Vec` rather than through `&mut str`
+        // so that it is valid for the whole allocation, not just the length.
+        let ptr = unsafe { o.as_mut_vec() }.as_mut_ptr();
+        match unsafe { Extent::try_owned(ptr, o.len(), o.capacity()) } {
+            Some(parts) => Ok(parts),
+            None => Err(ManuallyDrop::into_inner(o)),
+        }
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Ok(Arc::from(o))
     }
 
     #[inline]
@@ -87,14 +223,39 @@ unsafe impl<T: Clone> Convert for [T] {
     type Extent = Extent;
 
     #[inline]
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
-        unsafe { Extent::borrowed(b.as_ptr(), b.len()) }
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        unsafe { Extent::borrowed(ptr as *const T, ptr.len()) }
     }
 
     #[inline]
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
+    fn try_unmake_owned(mut o: Self::Owned) -> Result<Parts<Self>, Self::Owned> {
+        // The capacity of a `Vec` of zero-sized types is always `usize::MAX`,
+        // so the length is stored as the capacity instead. It is restored in
+        // `make_owned`.
+        let cap = if mem::size_of::<T>() == 0 {
+            o.len()
+        } else {
+            let cap = Extent::fit_capacity(o.len(), o.capacity());
+            if cap < o.capacity() {
+                o.shrink_to(cap);
+            }
+            if !Extent::can_pack(o.len(), o.capacity()) {
+                // Guarantees that the capacity is equal to the length.
+                o = o.into_boxed_slice().into_vec();
+            }
+            o.capacity()
+        };
         let mut o = ManuallyDrop::new(o);
-        unsafe { Extent::owned(o.as_mut_ptr(), o.len(), o.capacity()) }
+        match unsafe { Extent::try_owned(o.as_mut_ptr(), o.len(), cap) } {
+            Some(parts) => Ok(parts),
+            None => Err(ManuallyDrop::into_inner(o)),
+        }
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Ok(Arc::from(o))
     }
 
     #[inline]
@@ -104,82 +265,142 @@ unsafe impl<T: Clone> Convert for [T] {
 
     #[inline]
     unsafe fn make_owned(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> Self::Owned {
-        unsafe { Vec::from_raw_parts(ptr.as_ptr(), extra.len(), extra.capacity()) }
+        let cap = if mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            extra.capacity()
+        };
+        unsafe { Vec::from_raw_parts(ptr.as_ptr(), extra.len(), cap) }
     }
 }
 
 #[cfg(feature = "std")]
 unsafe impl Convert for std::ffi::CStr {
-    type Ptr = std::os::raw::c_char;
-    type Extent = bool;
+    type Ptr = u8;
+    type Extent = Extent;
 
     #[inline]
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
-        let ptr = unsafe { NonNull::new_unchecked(b.as_ptr() as *mut Self::Ptr) };
-        (ptr, false)
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        // The length of a `CStr` pointer includes the nul terminator.
+        unsafe { <[u8]>::unmake_ptr(ptr as *const [u8]) }
     }
 
     #[inline]
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
-        let ptr = unsafe { NonNull::new_unchecked(o.into_raw()) };
-        (ptr, true)
+    fn empty() -> (NonNull<Self::Ptr>, Self::Extent) {
+        // An empty `CStr` still has a nul terminator.
+        Self::unmake_borrowed(<&Self>::default())
     }
 
     #[inline]
-    unsafe fn make_ptr(ptr: NonNull<Self::Ptr>, _: Self::Extent) -> *const Self {
-        unsafe { Self::from_ptr(ptr.as_ptr()) as *const Self }
+    fn try_unmake_owned(o: Self::Owned) -> Result<Parts<Self>, Self::Owned> {
+        <[u8]>::try_unmake_owned(o.into_bytes_with_nul())
+            .map_err(|v| unsafe { std::ffi::CString::from_vec_with_nul_unchecked(v) })
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Ok(Arc::from(o))
+    }
+
+    #[inline]
+    unsafe fn make_ptr(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> *const Self {
+        unsafe { <[u8]>::make_ptr(ptr, extra) as *const Self }
     }
 
     #[inline]
-    unsafe fn make_owned(ptr: NonNull<Self::Ptr>, _: Self::Extent) -> Self::Owned {
-        unsafe { std::ffi::CString::from_raw(ptr.as_ptr()) }
+    unsafe fn make_owned(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> Self::Owned {
+        unsafe { std::ffi::CString::from_vec_with_nul_unchecked(<[u8]>::make_owned(ptr, extra)) }
     }
 }
 
-#[cfg(all(feature = "std", os_str_ext))]
+/// Returns the parts of an `OsStr` or `Path` pointer from its encoded bytes.
+///
+/// Only the address and length are taken from the bytes, the provenance is
+/// kept from the original pointer so that it is still valid for
+/// `Box::from_raw` and `Arc::from_raw`.
+///
+/// # Safety
+///
+/// The bytes must be the encoded bytes of the data behind the pointer.
+#[cfg(feature = "std")]
+#[inline]
+unsafe fn unmake_encoded<T: ?Sized>(ptr: *const T, bytes: &[u8]) -> (NonNull<u8>, Extent) {
+    let offset = bytes.as_ptr() as usize - ptr as *const u8 as usize;
+    unsafe { Extent::borrowed((ptr as *const u8).wrapping_add(offset), bytes.len()) }
+}
+
+#[cfg(feature = "std")]
 unsafe impl Convert for std::ffi::OsStr {
     type Ptr = u8;
     type Extent = Extent;
 
     #[inline]
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
-        <[u8]>::unmake_borrowed(b.as_bytes())
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        let bytes = unsafe { &*ptr }.as_encoded_bytes();
+        unsafe { unmake_encoded(ptr, bytes) }
     }
 
     #[inline]
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
-        <[u8]>::unmake_owned(o.into_vec())
+    fn try_unmake_owned(o: Self::Owned) -> Result<Parts<Self>, Self::Owned> {
+        <[u8]>::try_unmake_owned(o.into_encoded_bytes())
+            .map_err(|v| unsafe { std::ffi::OsString::from_encoded_bytes_unchecked(v) })
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Ok(Arc::from(o))
     }
 
     #[inline]
     unsafe fn make_ptr(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> *const Self {
-        unsafe { <[u8]>::make_ptr(ptr, extra) as *const Self }
+        let bytes = unsafe { <[u8]>::make_ptr(ptr, extra) };
+        // A reference from `from_encoded_bytes_unchecked` would not be valid
+        // for `Box::from_raw` or `Arc::from_raw`, so the pointer is cast and
+        // checked against it instead.
+        let os_str = bytes as *const Self;
+        debug_assert!(ptr::eq(os_str, unsafe {
+            Self::from_encoded_bytes_unchecked(&*bytes)
+        }));
+        os_str
     }
 
     #[inline]
     unsafe fn make_owned(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> Self::Owned {
-        unsafe { std::ffi::OsString::from_vec(<[u8]>::make_owned(ptr, extra)) }
+        unsafe { std::ffi::OsString::from_encoded_bytes_unchecked(<[u8]>::make_owned(ptr, extra)) }
     }
 }
 
-#[cfg(all(feature = "std", os_str_ext))]
+#[cfg(feature = "std")]
 unsafe impl Convert for std::path::Path {
     type Ptr = u8;
     type Extent = Extent;
 
     #[inline]
-    fn unmake_borrowed(b: &Self) -> (NonNull<Self::Ptr>, Self::Extent) {
-        std::ffi::OsStr::unmake_borrowed(b.as_os_str())
+    unsafe fn unmake_ptr(ptr: *const Self) -> (NonNull<Self::Ptr>, Self::Extent) {
+        let bytes = unsafe { &*ptr }.as_os_str().as_encoded_bytes();
+        unsafe { unmake_encoded(ptr, bytes) }
     }
 
     #[inline]
-    fn unmake_owned(o: Self::Owned) -> (NonNull<Self::Ptr>, Self::Extent) {
-        std::ffi::OsStr::unmake_owned(o.into_os_string())
+    fn try_unmake_owned(o: Self::Owned) -> Result<Parts<Self>, Self::Owned> {
+        std::ffi::OsStr::try_unmake_owned(o.into_os_string()).map_err(std::path::PathBuf::from)
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    fn try_to_shared(o: Self::Owned) -> Result<Arc<Self>, Self::Owned> {
+        Ok(Arc::from(o))
     }
 
     #[inline]
     unsafe fn make_ptr(ptr: NonNull<Self::Ptr>, extra: Self::Extent) -> *const Self {
-        unsafe { std::ffi::OsStr::make_ptr(ptr, extra) as *const Self }
+        let os_str = unsafe { std::ffi::OsStr::make_ptr(ptr, extra) };
+        // See the `OsStr` implementation.
+        let path = os_str as *const Self;
+        debug_assert!(ptr::eq(path, std::path::Path::new(unsafe { &*os_str })));
+        path
     }
 
     #[inline]
@@ -1,5 +1,11 @@
 //! Implements a compact storage for length and capacity.
+//!
+//! The highest byte of the word that stores the ownership tag is a [`Tag`]
+//! which only allows the values that are actually used. This means the
+//! compiler is able to use the remaining values as niches, for example
+//! `Option<Option<Cow<str>>>` is the same size as `Cow<str>`.
 
+use core::mem;
 use core::ptr::NonNull;
 
 #[cfg(not(target_pointer_width = "64"))]
@@ -7,40 +13,429 @@ pub use medium::Extent;
 #[cfg(target_pointer_width = "64")]
 pub use small::Extent;
 
+/// The valid values of the highest byte of a [`Word`].
+///
+/// This includes the values used by `SmallCow` to tag inline data.
+#[cfg(target_pointer_width = "64")]
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+#[repr(u8)]
+#[rustfmt::skip]
+pub enum Tag {
+    _00 = 0x00, _01 = 0x01, _02 = 0x02, _03 = 0x03, _04 = 0x04, _05 = 0x05, _06 = 0x06, _07 = 0x07,
+    _08 = 0x08, _09 = 0x09, _0A = 0x0A, _0B = 0x0B, _0C = 0x0C, _0D = 0x0D, _0E = 0x0E, _0F = 0x0F,
+    _10 = 0x10, _11 = 0x11, _12 = 0x12, _13 = 0x13, _14 = 0x14, _15 = 0x15, _16 = 0x16, _17 = 0x17,
+    _18 = 0x18, _19 = 0x19, _1A = 0x1A, _1B = 0x1B, _1C = 0x1C, _1D = 0x1D, _1E = 0x1E, _1F = 0x1F,
+    _20 = 0x20, _21 = 0x21, _22 = 0x22, _23 = 0x23, _24 = 0x24, _25 = 0x25, _26 = 0x26, _27 = 0x27,
+    _28 = 0x28, _29 = 0x29, _2A = 0x2A, _2B = 0x2B, _2C = 0x2C, _2D = 0x2D, _2E = 0x2E, _2F = 0x2F,
+    _30 = 0x30, _31 = 0x31, _32 = 0x32, _33 = 0x33, _34 = 0x34, _35 = 0x35, _36 = 0x36, _37 = 0x37,
+    _38 = 0x38, _39 = 0x39, _3A = 0x3A, _3B = 0x3B, _3C = 0x3C, _3D = 0x3D, _3E = 0x3E, _3F = 0x3F,
+    _40 = 0x40, _41 = 0x41, _42 = 0x42, _43 = 0x43, _44 = 0x44, _45 = 0x45, _46 = 0x46, _47 = 0x47,
+    _48 = 0x48, _49 = 0x49, _4A = 0x4A, _4B = 0x4B, _4C = 0x4C, _4D = 0x4D, _4E = 0x4E, _4F = 0x4F,
+    _80 = 0x80, _81 = 0x81, _82 = 0x82, _83 = 0x83, _84 = 0x84, _85 = 0x85, _86 = 0x86, _87 = 0x87,
+    _88 = 0x88, _89 = 0x89, _8A = 0x8A, _8B = 0x8B, _8C = 0x8C, _8D = 0x8D, _8E = 0x8E, _8F = 0x8F,
+    _90 = 0x90, _91 = 0x91, _92 = 0x92, _93 = 0x93, _94 = 0x94, _95 = 0x95, _96 = 0x96, _97 = 0x97,
+    _98 = 0x98, _99 = 0x99, _9A = 0x9A, _9B = 0x9B, _9C = 0x9C, _9D = 0x9D, _9E = 0x9E, _9F = 0x9F,
+    _A0 = 0xA0, _A1 = 0xA1, _A2 = 0xA2, _A3 = 0xA3, _A4 = 0xA4, _A5 = 0xA5, _A6 = 0xA6, _A7 = 0xA7,
+    _A8 = 0xA8, _A9 = 0xA9, _AA = 0xAA, _AB = 0xAB, _AC = 0xAC, _AD = 0xAD, _AE = 0xAE, _AF = 0xAF,
+    _B0 = 0xB0, _B1 = 0xB1, _B2 = 0xB2, _B3 = 0xB3, _B4 = 0xB4, _B5 = 0xB5, _B6 = 0xB6, _B7 = 0xB7,
+    _B8 = 0xB8, _B9 = 0xB9, _BA = 0xBA, _BB = 0xBB, _BC = 0xBC, _BD = 0xBD, _BE = 0xBE, _BF = 0xBF,
+    _C0 = 0xC0, _C1 = 0xC1, _C2 = 0xC2, _C3 = 0xC3, _C4 = 0xC4, _C5 = 0xC5, _C6 = 0xC6, _C7 = 0xC7,
+    _C8 = 0xC8, _C9 = 0xC9, _CA = 0xCA, _CB = 0xCB, _CC = 0xCC, _CD = 0xCD, _CE = 0xCE, _CF = 0xCF,
+    _D0 = 0xD0, _D1 = 0xD1, _D2 = 0xD2, _D3 = 0xD3, _D4 = 0xD4, _D5 = 0xD5, _D6 = 0xD6, _D7 = 0xD7,
+    _D8 = 0xD8, _D9 = 0xD9, _DA = 0xDA, _DB = 0xDB, _DC = 0xDC, _DD = 0xDD, _DE = 0xDE, _DF = 0xDF,
+    _E0 = 0xE0, _E1 = 0xE1, _E2 = 0xE2, _E3 = 0xE3, _E4 = 0xE4, _E5 = 0xE5, _E6 = 0xE6, _E7 = 0xE7,
+    _E8 = 0xE8, _E9 = 0xE9, _EA = 0xEA, _EB = 0xEB, _EC = 0xEC, _ED = 0xED, _EE = 0xEE, _EF = 0xEF,
+    _F0 = 0xF0, _F1 = 0xF1, _F2 = 0xF2, _F3 = 0xF3, _F4 = 0xF4, _F5 = 0xF5, _F6 = 0xF6, _F7 = 0xF7,
+    _F8 = 0xF8, _F9 = 0xF9, _FA = 0xFA, _FB = 0xFB, _FC = 0xFC, _FD = 0xFD, _FE = 0xFE, _FF = 0xFF,
+}
+
+/// The valid values of the highest byte of a [`Word`].
+///
+/// This includes the values used by `SmallCow` to tag inline data.
+#[cfg(not(target_pointer_width = "64"))]
+#[allow(dead_code)]
+#[derive(Clone, Copy)]
+#[repr(u8)]
+#[rustfmt::skip]
+pub enum Tag {
+    _00 = 0x00, _01 = 0x01, _02 = 0x02, _03 = 0x03, _04 = 0x04, _05 = 0x05, _06 = 0x06, _07 = 0x07,
+    _08 = 0x08, _09 = 0x09, _0A = 0x0A, _0B = 0x0B, _0C = 0x0C, _0D = 0x0D, _0E = 0x0E, _0F = 0x0F,
+    _10 = 0x10, _11 = 0x11, _12 = 0x12, _13 = 0x13, _14 = 0x14, _15 = 0x15, _16 = 0x16, _17 = 0x17,
+    _18 = 0x18, _19 = 0x19, _1A = 0x1A, _1B = 0x1B, _1C = 0x1C, _1D = 0x1D, _1E = 0x1E, _1F = 0x1F,
+    _20 = 0x20, _21 = 0x21, _22 = 0x22, _23 = 0x23, _24 = 0x24, _25 = 0x25, _26 = 0x26, _27 = 0x27,
+    _28 = 0x28, _29 = 0x29, _2A = 0x2A, _2B = 0x2B, _2C = 0x2C, _2D = 0x2D, _2E = 0x2E, _2F = 0x2F,
+    _30 = 0x30, _31 = 0x31, _32 = 0x32, _33 = 0x33, _34 = 0x34, _35 = 0x35, _36 = 0x36, _37 = 0x37,
+    _38 = 0x38, _39 = 0x39, _3A = 0x3A, _3B = 0x3B, _3C = 0x3C, _3D = 0x3D, _3E = 0x3E, _3F = 0x3F,
+    _40 = 0x40, _41 = 0x41, _42 = 0x42, _43 = 0x43, _44 = 0x44, _45 = 0x45, _46 = 0x46, _47 = 0x47,
+    _48 = 0x48, _49 = 0x49, _4A = 0x4A, _4B = 0x4B, _4C = 0x4C, _4D = 0x4D, _4E = 0x4E, _4F = 0x4F,
+    _50 = 0x50, _51 = 0x51, _52 = 0x52, _53 = 0x53, _54 = 0x54, _55 = 0x55, _56 = 0x56, _57 = 0x57,
+    _58 = 0x58, _59 = 0x59, _5A = 0x5A, _5B = 0x5B, _5C = 0x5C, _5D = 0x5D, _5E = 0x5E, _5F = 0x5F,
+    _60 = 0x60, _61 = 0x61, _62 = 0x62, _63 = 0x63, _64 = 0x64, _65 = 0x65, _66 = 0x66, _67 = 0x67,
+    _68 = 0x68, _69 = 0x69, _6A = 0x6A, _6B = 0x6B, _6C = 0x6C, _6D = 0x6D, _6E = 0x6E, _6F = 0x6F,
+    _70 = 0x70, _71 = 0x71, _72 = 0x72, _73 = 0x73, _74 = 0x74, _75 = 0x75, _76 = 0x76, _77 = 0x77,
+    _78 = 0x78, _79 = 0x79, _7A = 0x7A, _7B = 0x7B, _7C = 0x7C, _7D = 0x7D, _7E = 0x7E, _7F = 0x7F,
+    _80 = 0x80, _81 = 0x81, _82 = 0x82, _83 = 0x83, _84 = 0x84, _85 = 0x85, _86 = 0x86, _87 = 0x87,
+    _88 = 0x88, _89 = 0x89, _8A = 0x8A, _8B = 0x8B,
+}
+
+impl Tag {
+    #[inline]
+    const fn is_valid(v: u8) -> bool {
+        #[cfg(target_pointer_width = "64")]
+        {
+            matches!(v, 0x00..=0x4F | 0x80..=0xFF)
+        }
+        #[cfg(not(target_pointer_width = "64"))]
+        {
+            matches!(v, 0x00..=0x8B)
+        }
+    }
+
+    /// Returns the tag with the given value.
+    ///
+    /// # Panics
+    ///
+    /// If the value is not a valid tag.
+    #[inline]
+    pub const fn new(v: u8) -> Self {
+        assert!(Self::is_valid(v), "invalid tag");
+        // SAFETY: The value is a valid variant.
+        unsafe { mem::transmute::<u8, Self>(v) }
+    }
+
+    #[inline]
+    pub const fn get(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A word whose highest byte is a [`Tag`].
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct Word {
+    _align: [usize; 0],
+    #[cfg(target_endian = "big")]
+    tag: Tag,
+    rest: [u8; mem::size_of::<usize>() - 1],
+    #[cfg(target_endian = "little")]
+    tag: Tag,
+}
+
+impl Word {
+    /// Returns the word with the given value.
+    ///
+    /// # Safety
+    ///
+    /// The highest byte of the value must be a valid [`Tag`].
+    #[inline]
+    const unsafe fn new_unchecked(v: usize) -> Self {
+        debug_assert!(Tag::is_valid((v >> (usize::BITS - 8)) as u8));
+        unsafe { mem::transmute::<usize, Self>(v) }
+    }
+
+    #[inline]
+    const fn get(self) -> usize {
+        // SAFETY: Any word is a valid `usize`.
+        unsafe { mem::transmute::<Self, usize>(self) }
+    }
+}
+
+/// A length and an ownership tag, for owned data that has no capacity.
+///
+/// On 64-bit platforms the highest byte is a [`Tag`] so that the same niches
+/// are available as for [`Extent`]. On 32-bit platforms the whole word is
+/// needed for the length.
+#[derive(Clone, Copy)]
+pub struct BoxExtent(
+    #[cfg(target_pointer_width = "64")] Word,
+    #[cfg(not(target_pointer_width = "64"))] usize,
+);
+
+impl BoxExtent {
+    const OWNED: usize = 1 << (usize::BITS - 1);
+
+    // The highest byte is at most 0x3F when borrowed and at most 0xBF when
+    // owned, which are both valid tags.
+    #[cfg(target_pointer_width = "64")]
+    const MAX_LEN: usize = (1 << (usize::BITS - 2)) - 1;
+
+    #[cfg(not(target_pointer_width = "64"))]
+    const MAX_LEN: usize = isize::MAX as usize;
+
+    /// # Safety
+    ///
+    /// On 64-bit platforms the highest byte must be a valid [`Tag`], this is
+    /// the case for all extents constructed below.
+    #[inline]
+    const unsafe fn new(bits: usize) -> Self {
+        #[cfg(target_pointer_width = "64")]
+        {
+            Self(unsafe { Word::new_unchecked(bits) })
+        }
+        #[cfg(not(target_pointer_width = "64"))]
+        {
+            Self(bits)
+        }
+    }
+
+    #[inline]
+    const fn bits(&self) -> usize {
+        #[cfg(target_pointer_width = "64")]
+        {
+            self.0.get()
+        }
+        #[cfg(not(target_pointer_width = "64"))]
+        {
+            self.0
+        }
+    }
+
+    #[inline]
+    pub const fn borrowed(len: usize) -> Self {
+        assert!(len <= Self::MAX_LEN, "length out of bounds");
+        // SAFETY: The length is checked above.
+        unsafe { Self::new(len) }
+    }
+
+    #[inline]
+    pub const fn owned(len: usize) -> Self {
+        assert!(len <= Self::MAX_LEN, "length out of bounds");
+        // SAFETY: The length is checked above.
+        unsafe { Self::new(Self::OWNED | len) }
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.bits() & !Self::OWNED
+    }
+
+    #[inline]
+    pub const fn is_owned(&self) -> bool {
+        self.bits() & Self::OWNED != 0
+    }
+}
+
 #[cfg(target_pointer_width = "64")]
 mod small {
     use super::*;
 
     const SHIFT: u32 = usize::BITS / 2;
     const LOWER: usize = usize::MAX >> SHIFT;
-    const UPPER: usize = !LOWER;
+    const UPPER: usize = !LOWER & !(LARGE | INLINE | OWNED);
+
+    // If set then the length is stored in the lower 61 bits and the capacity
+    // is either zero or equal to the length depending on the `OWNED` bit. If
+    // the `SHARED` bit is set instead then the data is reference counted.
+    const LARGE: usize = 1 << (usize::BITS - 1);
+    const SHARED: usize = 1 << (usize::BITS - 2);
+    const LARGE_LEN: usize = !(LARGE | SHARED | OWNED);
 
-    #[derive(Clone, Copy, Default)]
-    pub struct Extent(usize);
+    // If all of these are set then the length is stored in the lower 40 bits
+    // and the capacity is stored as a 16 bit mantissa and a 5 bit exponent in
+    // the bits above it. This keeps the capacity of large owned data, as long
+    // as it is a multiple of a large enough power of two, which is the case
+    // for capacities grown by doubling.
+    const CAPPED: usize = LARGE | SHARED | OWNED;
+    const CAPPED_LEN: usize = (1 << 40) - 1;
+    const MANTISSA_SHIFT: u32 = 40;
+    const MANTISSA: usize = (1 << 16) - 1;
+    const EXPONENT_SHIFT: u32 = 56;
+    const EXPONENT: u32 = (1 << 5) - 1;
+
+    /// Returns the mantissa and exponent of the largest capacity that is at
+    /// most the given one and can be stored in a capped extent.
+    #[inline]
+    const fn round_capacity(cap: usize) -> (usize, u32) {
+        let bits = usize::BITS - cap.leading_zeros();
+        let exp = bits.saturating_sub(MANTISSA.count_ones());
+        if exp > EXPONENT {
+            (MANTISSA, EXPONENT)
+        } else {
+            (cap >> exp, exp)
+        }
+    }
+
+    // If set and `LARGE` is not set then this is not an extent at all, the
+    // data is stored inline by `SmallCow`.
+    const INLINE: usize = 1 << (usize::BITS - 2);
+
+    // Set if the data is owned, regardless of whether `LARGE` is set.
+    const OWNED: usize = 1 << (usize::BITS - 3);
+
+    /// The length, capacity, and ownership tag of the data in a compact
+    /// [`Cow`][crate::Cow].
+    ///
+    /// On 64-bit platforms this is one word wide.
+    #[derive(Clone, Copy)]
+    pub struct Extent(Word);
+
+    impl Default for Extent {
+        #[inline]
+        fn default() -> Self {
+            // SAFETY: The highest byte is zero.
+            unsafe { Self::from_bits(0) }
+        }
+    }
 
     impl Extent {
+        /// # Safety
+        ///
+        /// The highest byte must be a valid [`Tag`], this is the case for all
+        /// extents constructed below.
+        #[inline]
+        const unsafe fn from_bits(bits: usize) -> Self {
+            Self(unsafe { Word::new_unchecked(bits) })
+        }
+
+        #[inline]
+        const fn bits(&self) -> usize {
+            self.0.get()
+        }
+
+        /// The bits of the highest byte that tag inline data.
+        pub(crate) const INLINE_MASK: u8 = ((LARGE | INLINE) >> (usize::BITS - 8)) as u8;
+
+        /// The value of the masked highest byte if the data is inline.
+        pub(crate) const INLINE_TAG: u8 = (INLINE >> (usize::BITS - 8)) as u8;
+
+        /// Whether the given length and capacity can be stored as is.
+        ///
+        /// If not, then the capacity must be shrunk to
+        /// [`Extent::fit_capacity`] before calling [`Extent::try_owned`].
         #[inline]
-        pub unsafe fn borrowed<T>(ptr: *const T, len: usize) -> (NonNull<T>, Self) {
+        pub const fn can_pack(len: usize, cap: usize) -> bool {
+            Self::is_small(len, cap)
+                || Self::is_capped(len, cap)
+                || (cap == len && len <= LARGE_LEN)
+        }
+
+        /// Returns the largest capacity that is at most the given one and can
+        /// be stored with the given length.
+        ///
+        /// This is the length itself if no larger capacity can be stored.
+        #[inline]
+        pub const fn fit_capacity(len: usize, cap: usize) -> usize {
+            if Self::is_small(len, cap) {
+                return cap;
+            }
+            let (mantissa, exp) = round_capacity(cap);
+            if len <= CAPPED_LEN && mantissa << exp >= len {
+                mantissa << exp
+            } else {
+                len
+            }
+        }
+
+        #[inline]
+        const fn is_small(len: usize, cap: usize) -> bool {
+            len <= LOWER && cap <= UPPER >> SHIFT
+        }
+
+        #[inline]
+        const fn is_capped(len: usize, cap: usize) -> bool {
+            let (mantissa, exp) = round_capacity(cap);
+            len <= CAPPED_LEN && mantissa << exp == cap
+        }
+
+        /// Returns the parts for borrowed data.
+        ///
+        /// # Panics
+        ///
+        /// If the length is too large to be stored.
+        ///
+        /// # Safety
+        ///
+        /// The pointer must be non-null.
+        #[inline]
+        pub const unsafe fn borrowed<T>(ptr: *const T, len: usize) -> (NonNull<T>, Self) {
+            let extra = if len <= LOWER {
+                len
+            } else {
+                assert!(len <= LARGE_LEN, "length out of bounds");
+                LARGE | len
+            };
             let ptr = unsafe { NonNull::new_unchecked(ptr as *mut T) };
-            (ptr, Self(len))
+            (ptr, unsafe { Self::from_bits(extra) })
         }
 
+        /// Returns the parts for owned data, or `None` if the length and
+        /// capacity cannot be stored.
+        ///
+        /// # Safety
+        ///
+        /// The pointer must be non-null.
         #[inline]
-        pub unsafe fn owned<T>(ptr: *mut T, len: usize, cap: usize) -> (NonNull<T>, Self) {
-            assert!(cap & LOWER == cap, "capacity out of bounds: {}", cap);
-            let extra = (cap << SHIFT) | len;
+        pub unsafe fn try_owned<T>(
+            ptr: *mut T,
+            len: usize,
+            cap: usize,
+        ) -> Option<(NonNull<T>, Self)> {
+            let extra = if Self::is_small(len, cap) {
+                OWNED | (cap << SHIFT) | len
+            } else if Self::is_capped(len, cap) {
+                let (mantissa, exp) = round_capacity(cap);
+                CAPPED | ((exp as usize) << EXPONENT_SHIFT) | (mantissa << MANTISSA_SHIFT) | len
+            } else if cap == len && len <= LARGE_LEN {
+                LARGE | OWNED | len
+            } else {
+                return None;
+            };
             let ptr = unsafe { NonNull::new_unchecked(ptr) };
-            (ptr, Self(extra))
+            Some((ptr, unsafe { Self::from_bits(extra) }))
+        }
+
+        /// Converts a borrowed extent into a shared one.
+        #[inline]
+        pub const fn into_shared(self) -> Self {
+            unsafe { Self::from_bits(LARGE | SHARED | self.len()) }
+        }
+
+        /// Returns true if the data is shared.
+        #[inline]
+        pub const fn is_shared(&self) -> bool {
+            self.bits() & CAPPED == LARGE | SHARED
         }
 
+        /// Returns true if the data is owned.
+        #[inline]
+        pub const fn is_owned(&self) -> bool {
+            self.bits() & OWNED != 0
+        }
+
+        /// Returns true if the length of the data is zero.
+        #[inline]
+        pub const fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the length of the data.
         #[inline]
         pub const fn len(&self) -> usize {
-            self.0 & LOWER
+            if self.bits() & LARGE == 0 {
+                self.bits() & LOWER
+            } else if self.bits() & CAPPED == CAPPED {
+                self.bits() & CAPPED_LEN
+            } else {
+                self.bits() & LARGE_LEN
+            }
         }
 
+        /// Returns the capacity of the data, this is zero if the data is not
+        /// owned.
         #[inline]
         pub const fn capacity(&self) -> usize {
-            (self.0 & UPPER) >> SHIFT
+            if !self.is_owned() {
+                0
+            } else if self.bits() & LARGE == 0 {
+                (self.bits() & UPPER) >> SHIFT
+            } else if self.bits() & CAPPED == CAPPED {
+                let mantissa = (self.bits() >> MANTISSA_SHIFT) & MANTISSA;
+                let exp = (self.bits() >> EXPONENT_SHIFT) as u32 & EXPONENT;
+                mantissa << exp
+            } else {
+                self.bits() & LARGE_LEN
+            }
         }
     }
 }
@@ -49,33 +444,161 @@ mod small {
 mod medium {
     use super::*;
 
-    #[derive(Clone, Copy, Default)]
+    // The capacity of an owned `str` never exceeds `isize::MAX` so if the
+    // highest bit is set then the data is stored inline by `SmallCow`.
+    const INLINE: usize = 1 << (usize::BITS - 1);
+
+    // The capacity of any owned data that is stored is at most `isize::MAX` so
+    // this value is free to mark shared data. `SmallCow` never
+    // shares its data so this does not conflict with `INLINE`.
+    const SHARED: usize = 1 << (usize::BITS - 1);
+
+    // The length of any data that is stored is at most `isize::MAX` so the
+    // highest bit of the length is free to mark owned data.
+    const OWNED: usize = 1 << (usize::BITS - 1);
+
+    /// The length, capacity, and ownership tag of the data in a compact
+    /// [`Cow`][crate::Cow].
+    ///
+    /// On 32-bit platforms this is two words wide.
+    //
+    // The capacity is stored in the word at the end of `SmallCow` that holds
+    // the inline tag, which depends on the endianness.
+    #[derive(Clone, Copy)]
+    #[repr(C)]
     pub struct Extent {
+        #[cfg(target_endian = "big")]
+        cap: Word,
         len: usize,
-        cap: usize,
+        #[cfg(target_endian = "little")]
+        cap: Word,
+    }
+
+    impl Default for Extent {
+        #[inline]
+        fn default() -> Self {
+            // SAFETY: The highest byte is zero.
+            unsafe { Self::new(0, 0) }
+        }
     }
 
     impl Extent {
+        /// # Safety
+        ///
+        /// The highest byte of the capacity must be a valid [`Tag`], this is
+        /// the case for all extents constructed below.
         #[inline]
-        pub unsafe fn borrowed<T>(ptr: *const T, len: usize) -> (NonNull<T>, Self) {
+        const unsafe fn new(len: usize, cap: usize) -> Self {
+            Self {
+                len,
+                cap: unsafe { Word::new_unchecked(cap) },
+            }
+        }
+
+        /// The bits of the highest byte that tag inline data.
+        pub(crate) const INLINE_MASK: u8 = (INLINE >> (usize::BITS - 8)) as u8;
+
+        /// The value of the masked highest byte if the data is inline.
+        pub(crate) const INLINE_TAG: u8 = (INLINE >> (usize::BITS - 8)) as u8;
+
+        /// Whether the given length and capacity can be stored as is.
+        ///
+        /// If not, then the capacity must be shrunk to
+        /// [`Extent::fit_capacity`] before calling [`Extent::try_owned`].
+        #[inline]
+        pub const fn can_pack(len: usize, cap: usize) -> bool {
+            len <= isize::MAX as usize && cap <= isize::MAX as usize
+        }
+
+        /// Returns the largest capacity that is at most the given one and can
+        /// be stored with the given length.
+        ///
+        /// This is the length itself if no larger capacity can be stored.
+        #[inline]
+        pub const fn fit_capacity(len: usize, cap: usize) -> usize {
+            if cap <= isize::MAX as usize {
+                cap
+            } else {
+                len
+            }
+        }
+
+        /// Returns the parts for borrowed data.
+        ///
+        /// # Panics
+        ///
+        /// If the length is too large to be stored.
+        ///
+        /// # Safety
+        ///
+        /// The pointer must be non-null.
+        #[inline]
+        pub const unsafe fn borrowed<T>(ptr: *const T, len: usize) -> (NonNull<T>, Self) {
+            assert!(len & OWNED == 0, "length out of bounds");
             let ptr = unsafe { NonNull::new_unchecked(ptr as *mut T) };
-            (ptr, Self { len, cap: 0 })
+            (ptr, unsafe { Self::new(len, 0) })
         }
 
+        /// Returns the parts for owned data, or `None` if the length and
+        /// capacity cannot be stored.
+        ///
+        /// # Safety
+        ///
+        /// The pointer must be non-null.
         #[inline]
-        pub unsafe fn owned<T>(ptr: *mut T, len: usize, cap: usize) -> (NonNull<T>, Self) {
+        pub unsafe fn try_owned<T>(
+            ptr: *mut T,
+            len: usize,
+            cap: usize,
+        ) -> Option<(NonNull<T>, Self)> {
+            // Larger values would overlap with `OWNED`, `SHARED`, and the
+            // inline tag.
+            if len > isize::MAX as usize || cap > isize::MAX as usize {
+                return None;
+            }
             let ptr = unsafe { NonNull::new_unchecked(ptr) };
-            (ptr, Self { len, cap })
+            Some((ptr, unsafe { Self::new(OWNED | len, cap) }))
+        }
+
+        /// Converts a borrowed extent into a shared one.
+        #[inline]
+        pub const fn into_shared(self) -> Self {
+            unsafe { Self::new(self.len(), SHARED) }
+        }
+
+        /// Returns true if the data is shared.
+        #[inline]
+        pub const fn is_shared(&self) -> bool {
+            self.cap.get() == SHARED
+        }
+
+        /// Returns true if the data is owned.
+        #[inline]
+        pub const fn is_owned(&self) -> bool {
+            self.len & OWNED != 0
+        }
+
+        /// Returns true if the length of the data is zero.
+        #[inline]
+        pub const fn is_empty(&self) -> bool {
+            self.len() == 0
         }
 
+        /// Returns the length of the data.
         #[inline]
         pub const fn len(&self) -> usize {
-            self.len
+            self.len & !OWNED
         }
 
+        /// Returns the capacity of the data, this is zero if the data is not
+        /// owned.
         #[inline]
         pub const fn capacity(&self) -> usize {
-            self.cap
+            if self.is_owned() {
+                self.cap.get()
+            } else {
+                0
+            }
         }
     }
 }
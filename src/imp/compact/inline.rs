@@ -0,0 +1,294 @@
+//! A compact `Cow<str>` that stores short owned strings inline.
+//!
+//! The highest byte of the extent of a compact `Cow<str>` never has the
+//! [`Extent::INLINE_TAG`] bits set. When the data is inline, this byte stores
+//! the tag and the length and the rest of the bytes store the string data. The
+//! field order of the compact `Cow` and the extent is chosen so that this byte
+//! is the last byte on little endian platforms and the first byte on big endian
+//! platforms, which means the string data is always contiguous.
+//!
+//! The tag byte is a [`Tag`] in both representations, so `SmallCow` has the
+//! same niches as the compact `Cow`.
+
+use core::marker::PhantomData;
+use core::mem;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+use core::slice;
+use core::str;
+
+use alloc::borrow::Cow as StdCow;
+use alloc::string::String;
+
+use crate::imp;
+use crate::imp::Cow as _;
+
+use super::extent::{Extent, Tag};
+use super::CowMut;
+
+type Heap = super::Cow<str>;
+
+/// The size of the `Cow` in bytes.
+const SIZE: usize = mem::size_of::<Heap>();
+
+/// The maximum length of inline data.
+pub const CAPACITY: usize = SIZE - 1;
+
+/// The representation of inline data, the tag byte overlaps the highest byte
+/// of the extent of the heap representation.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Repr {
+    _align: [usize; 0],
+    #[cfg(target_endian = "big")]
+    tag: Tag,
+    data: [MaybeUninit<u8>; CAPACITY],
+    #[cfg(target_endian = "little")]
+    tag: Tag,
+}
+
+const _: () = assert!(mem::size_of::<Repr>() == SIZE);
+const _: () = assert!(mem::align_of::<Repr>() == mem::align_of::<Heap>());
+
+/// A compact clone-on-write smart pointer with inline storage.
+pub struct Cow {
+    /// Either inline data or a `Heap` depending on the tag.
+    repr: Repr,
+
+    /// For drop check, the `Heap` is dropped with the `Cow`.
+    marker: PhantomData<Heap>,
+}
+
+impl Cow {
+    #[inline]
+    fn heap(heap: Heap) -> Self {
+        let heap = ManuallyDrop::new(heap);
+        Self {
+            // SAFETY: `Repr` has the same size and alignment as `Heap` and the
+            // highest byte of the extent is always a valid `Tag`.
+            repr: unsafe { mem::transmute_copy::<Heap, Repr>(&heap) },
+            marker: PhantomData,
+        }
+    }
+
+    /// Stores the given string inline, it must not be longer than `CAPACITY`.
+    #[inline]
+    pub fn inline(s: &str) -> Self {
+        debug_assert!(s.len() <= CAPACITY);
+        let mut data = [MaybeUninit::new(0); CAPACITY];
+        for (d, b) in data.iter_mut().zip(s.as_bytes()) {
+            *d = MaybeUninit::new(*b);
+        }
+        Self {
+            repr: Repr {
+                _align: [],
+                tag: Tag::new(Extent::INLINE_TAG | s.len() as u8),
+                data,
+            },
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a pointer to the heap representation.
+    #[inline]
+    fn heap_ptr(&self) -> *const Heap {
+        &self.repr as *const Repr as *const Heap
+    }
+
+    /// Returns a mutable pointer to the heap representation.
+    #[inline]
+    fn heap_mut_ptr(&mut self) -> *mut Heap {
+        &mut self.repr as *mut Repr as *mut Heap
+    }
+
+    /// Whether the data is stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.repr.tag.get() & Extent::INLINE_MASK == Extent::INLINE_TAG
+    }
+
+    /// Returns the heap representation, moving inline data to the heap.
+    #[inline]
+    fn heap_mut(&mut self) -> &mut Heap {
+        if self.is_inline() {
+            *self = Self::heap(Heap::owned(String::from(self.make_ref())));
+        }
+        // SAFETY: The data is not inline.
+        unsafe { &mut *self.heap_mut_ptr() }
+    }
+
+    /// Returns the heap representation, or `None` if the data is inline.
+    #[inline]
+    fn as_heap(&self) -> Option<&Heap> {
+        if self.is_inline() {
+            None
+        } else {
+            // SAFETY: The data is not inline.
+            Some(unsafe { &*self.heap_ptr() })
+        }
+    }
+
+    /// Converts into the heap representation, or returns `self` if the data is
+    /// inline.
+    #[inline]
+    fn into_heap(self) -> Result<Heap, Self> {
+        if self.is_inline() {
+            Err(self)
+        } else {
+            let this = ManuallyDrop::new(self);
+            // SAFETY: The data is not inline and `self` is never dropped.
+            Ok(unsafe { ptr::read(this.heap_ptr()) })
+        }
+    }
+}
+
+impl imp::Cow<str> for Cow {
+    type Mut<'c>
+        = CowMut<'c, str>
+    where
+        Self: 'c;
+
+    #[inline]
+    fn borrowed(b: &str) -> Self {
+        Self::heap(Heap::borrowed(b))
+    }
+
+    #[inline]
+    fn owned(o: String) -> Self {
+        if o.len() <= CAPACITY {
+            Self::inline(&o)
+        } else {
+            Self::heap(Heap::owned(o))
+        }
+    }
+
+    #[inline]
+    fn try_owned(o: String) -> Result<Self, String> {
+        if o.len() <= CAPACITY {
+            Ok(Self::inline(&o))
+        } else {
+            Heap::try_owned(o).map(Self::heap)
+        }
+    }
+
+    #[inline]
+    fn is_borrowed(&self) -> bool {
+        self.as_heap().is_some_and(Heap::is_borrowed)
+    }
+
+    #[inline]
+    fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+
+    #[inline]
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn make_ref(&self) -> &str {
+        match self.as_heap() {
+            Some(heap) => heap.make_ref(),
+            None => {
+                let len = (self.repr.tag.get() & !Extent::INLINE_MASK) as usize;
+                // SAFETY: The data is inline so the whole array is initialized
+                // and the data was copied from a valid `str`.
+                unsafe {
+                    let data = slice::from_raw_parts(self.repr.data.as_ptr() as *const u8, len);
+                    str::from_utf8_unchecked(data)
+                }
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a str> {
+        // SAFETY: The caller guarantees that the data outlives `'a`.
+        self.as_heap()
+            .and_then(|heap| unsafe { heap.as_borrowed() })
+    }
+
+    #[inline]
+    fn into_owned(self) -> String {
+        match self.into_heap() {
+            Ok(heap) => heap.into_owned(),
+            Err(this) => String::from(this.make_ref()),
+        }
+    }
+
+    #[inline]
+    unsafe fn into_std<'a>(self) -> StdCow<'a, str> {
+        match self.into_heap() {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Ok(heap) => unsafe { heap.into_std() },
+            Err(this) => StdCow::Owned(String::from(this.make_ref())),
+        }
+    }
+
+    /// Short owned strings are stored inline so sharing is not supported.
+    #[inline]
+    fn share(&mut self) {}
+
+    #[inline]
+    fn to_mut(&mut self) -> CowMut<'_, str> {
+        self.heap_mut().to_mut()
+    }
+
+    #[inline]
+    fn apply<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut String),
+    {
+        if self.checked_apply(f).is_err() {
+            panic!("capacity out of bounds");
+        }
+    }
+
+    #[inline]
+    fn checked_apply<F>(&mut self, f: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut String),
+    {
+        if !self.is_inline() && self.is_owned() {
+            // SAFETY: The data is not inline.
+            return unsafe { &mut *self.heap_mut_ptr() }.checked_apply(f);
+        }
+        // Allows the result to be stored inline if it is short enough.
+        let mut o = String::from(self.make_ref());
+        f(&mut o);
+        match Self::try_owned(o) {
+            Ok(cow) => {
+                *self = cow;
+                Ok(())
+            }
+            Err(o) => {
+                *self = Self::borrowed("");
+                Err(o)
+            }
+        }
+    }
+}
+
+impl Clone for Cow {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self.as_heap() {
+            Some(heap) => Self::heap(heap.clone()),
+            None => Self {
+                repr: self.repr,
+                marker: PhantomData,
+            },
+        }
+    }
+}
+
+impl Drop for Cow {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.is_inline() {
+            // SAFETY: The data is not inline and is never used again.
+            unsafe { ptr::drop_in_place(self.heap_mut_ptr()) }
+        }
+    }
+}
@@ -1,39 +1,163 @@
 //! A better `Cow` implementation.
 
+mod boxed;
 mod convert;
 mod extent;
+pub mod inline;
+
+pub use self::boxed::Cow as BoxCow;
+pub use self::convert::{Convert, IsOwned, Parts};
+pub use self::extent::Extent;
 
 use core::marker::PhantomData;
 use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
 use core::ptr::NonNull;
 
+use alloc::borrow::Cow as StdCow;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
 use crate::imp;
 use crate::imp::Cow as _;
 
-use self::convert::{Convert, IsOwned};
-
 /// A compact clone-on-write smart pointer.
-pub struct Cow<'a, T>
+///
+/// This stores a pointer and the [`Extent`][Convert::Extent] of the data.
+//
+// The field order is fixed so that the highest byte of the extent is at a
+// known position, see `inline::Cow`.
+#[repr(C)]
+pub struct Cow<T>
 where
     T: ?Sized + Convert,
 {
+    /// Any extra data that is required to reconstruct an owned or borrowed
+    /// variant of this type. For example: length and capacity.
+    #[cfg(target_endian = "big")]
+    extent: T::Extent,
+
     /// Pointer to the data.
     ptr: NonNull<T::Ptr>,
 
     /// Any extra data that is required to reconstruct an owned or borrowed
     /// variant of this type. For example: length and capacity.
+    #[cfg(target_endian = "little")]
     extent: T::Extent,
 
-    /// For the lifetime.
-    marker: PhantomData<&'a T>,
+    /// For drop check, owned data is dropped with the `Cow`.
+    marker: PhantomData<T::Owned>,
+}
+
+/// A guard providing mutable access to the owned data of a compact `Cow`.
+pub struct CowMut<'c, T>
+where
+    T: ?Sized + Convert,
+{
+    cow: &'c mut Cow<T>,
+    owned: ManuallyDrop<T::Owned>,
+}
+
+impl<T> Cow<T>
+where
+    T: ?Sized + Convert,
+{
+    /// Constructs a borrowed `Cow` from its parts.
+    ///
+    /// This is used to construct a `Cow` in a constant context.
+    ///
+    /// # Safety
+    ///
+    /// The parts must have been returned by [`Extent::borrowed`] for valid
+    /// borrowed data of this type.
+    #[inline]
+    pub(crate) const unsafe fn from_borrowed_parts(parts: Parts<T>) -> Self {
+        let (ptr, extent) = parts;
+        Self {
+            ptr,
+            extent,
+            marker: PhantomData,
+        }
+    }
+
+    /// Replaces the parts with an empty borrowed value.
+    #[inline]
+    fn set_empty(&mut self) {
+        let (ptr, extent) = T::empty();
+        self.ptr = ptr;
+        self.extent = extent;
+    }
+
+    /// Returns the owned data, cloning it if it is borrowed or shared.
+    ///
+    /// If the data is owned or shared then `self` is left in an empty borrowed
+    /// state and the caller is responsible for writing the owned data back.
+    #[inline]
+    fn take_owned(&mut self) -> T::Owned {
+        if self.is_owned() {
+            // SAFETY:  This is safe because we temporarily replace the parts
+            // with an empty borrowed value. Therefore, if the caller had to
+            // panic, no double drop would occur and the `Cow` is still valid.
+            let o = unsafe { T::make_owned(self.ptr, self.extent) };
+            self.set_empty();
+            o
+        } else if self.is_shared() {
+            let o = self.make_ref().to_owned();
+            // SAFETY: Same as above, the reference count is only decremented
+            // once the parts no longer encode a "shared" version.
+            let (ptr, extent) = (self.ptr, self.extent);
+            self.set_empty();
+            unsafe { Self::release_shared(ptr, extent) };
+            o
+        } else {
+            self.make_ref().to_owned()
+        }
+    }
+
+    /// Increments the reference count of shared data.
+    ///
+    /// Shared data only exists on targets with atomic pointers, elsewhere this
+    /// does nothing.
+    ///
+    /// # Safety
+    ///
+    /// The parts must describe shared data.
+    #[inline]
+    unsafe fn retain_shared(ptr: NonNull<T::Ptr>, extent: T::Extent) {
+        #[cfg(target_has_atomic = "ptr")]
+        unsafe {
+            Arc::increment_strong_count(T::make_ptr(ptr, extent))
+        }
+        #[cfg(not(target_has_atomic = "ptr"))]
+        let _ = (ptr, extent);
+    }
+
+    /// Decrements the reference count of shared data, dropping it if this was
+    /// the last reference.
+    ///
+    /// # Safety
+    ///
+    /// The parts must describe shared data and must not be used again.
+    #[inline]
+    unsafe fn release_shared(ptr: NonNull<T::Ptr>, extent: T::Extent) {
+        #[cfg(target_has_atomic = "ptr")]
+        drop(unsafe { Arc::from_raw(T::make_ptr(ptr, extent)) });
+        #[cfg(not(target_has_atomic = "ptr"))]
+        let _ = (ptr, extent);
+    }
 }
 
-impl<'a, T> imp::Cow<'a, T> for Cow<'a, T>
+impl<T> imp::Cow<T> for Cow<T>
 where
     T: ?Sized + Convert,
 {
+    type Mut<'c>
+        = CowMut<'c, T>
+    where
+        Self: 'c;
+
     #[inline]
-    fn borrowed(b: &'a T) -> Self {
+    fn borrowed(b: &T) -> Self {
         let (ptr, extent) = T::unmake_borrowed(b);
         Self {
             ptr,
@@ -52,9 +176,19 @@ where
         }
     }
 
+    #[inline]
+    fn try_owned(o: T::Owned) -> Result<Self, T::Owned> {
+        let (ptr, extent) = T::try_unmake_owned(o)?;
+        Ok(Self {
+            ptr,
+            extent,
+            marker: PhantomData,
+        })
+    }
+
     #[inline]
     fn is_borrowed(&self) -> bool {
-        !self.extent.is_owned()
+        !self.extent.is_owned() && !self.extent.is_shared()
     }
 
     #[inline]
@@ -62,6 +196,11 @@ where
         self.extent.is_owned()
     }
 
+    #[inline]
+    fn is_shared(&self) -> bool {
+        self.extent.is_shared()
+    }
+
     #[inline]
     fn make_ref(&self) -> &T {
         // SAFETY: This is valid for both owned and borrowed variants.
@@ -78,29 +217,82 @@ where
         }
     }
 
+    #[inline]
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T> {
+        if self.is_borrowed() {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Some(unsafe { &*T::make_ptr(self.ptr, self.extent) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn into_std<'a>(self) -> StdCow<'a, T> {
+        if self.is_shared() {
+            return StdCow::Owned(self.make_ref().to_owned());
+        }
+        let cow = ManuallyDrop::new(self);
+        if cow.is_owned() {
+            StdCow::Owned(unsafe { T::make_owned(cow.ptr, cow.extent) })
+        } else {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            StdCow::Borrowed(unsafe { &*T::make_ptr(cow.ptr, cow.extent) })
+        }
+    }
+
+    #[inline]
+    fn share(&mut self) {
+        #[cfg(target_has_atomic = "ptr")]
+        if self.is_owned() {
+            let (ptr, extent) = match T::try_to_shared(self.take_owned()) {
+                Ok(a) => T::unmake_shared(a),
+                // The data was stored before so it can be stored again.
+                Err(o) => T::unmake_owned(o),
+            };
+            self.ptr = ptr;
+            self.extent = extent;
+        }
+    }
+
+    #[inline]
+    fn to_mut(&mut self) -> CowMut<'_, T> {
+        let owned = ManuallyDrop::new(self.take_owned());
+        CowMut { cow: self, owned }
+    }
+
     #[inline]
     fn apply<F>(&mut self, f: F)
     where
         F: FnOnce(&mut T::Owned),
     {
-        let mut o = if self.is_owned() {
-            // SAFETY:  This is safe because we temporarily set the `extent` to
-            // its default value which should encode a "borrowed" version.
-            // Therefore, if `f` had to panic, no double drop would occur.
-            let o = unsafe { T::make_owned(self.ptr, self.extent) };
-            self.extent = T::Extent::default();
-            o
-        } else {
-            self.make_ref().to_owned()
-        };
+        if self.checked_apply(f).is_err() {
+            panic!("capacity out of bounds");
+        }
+    }
+
+    #[inline]
+    fn checked_apply<F>(&mut self, f: F) -> Result<(), T::Owned>
+    where
+        F: FnOnce(&mut T::Owned),
+    {
+        let mut o = self.take_owned();
         f(&mut o);
-        let (ptr, extent) = T::unmake_owned(o);
-        self.ptr = ptr;
-        self.extent = extent;
+        match T::try_unmake_owned(o) {
+            Ok((ptr, extent)) => {
+                self.ptr = ptr;
+                self.extent = extent;
+                Ok(())
+            }
+            Err(o) => {
+                self.set_empty();
+                Err(o)
+            }
+        }
     }
 }
 
-impl<T> Clone for Cow<'_, T>
+impl<T> Clone for Cow<T>
 where
     T: ?Sized + Convert,
 {
@@ -109,12 +301,17 @@ where
         if self.is_owned() {
             Self::owned(self.make_ref().to_owned())
         } else {
+            if self.is_shared() {
+                // SAFETY: The data is shared and the new reference count is
+                // taken over by the clone.
+                unsafe { Self::retain_shared(self.ptr, self.extent) };
+            }
             Self { ..*self }
         }
     }
 }
 
-impl<T> Drop for Cow<'_, T>
+impl<T> Drop for Cow<T>
 where
     T: ?Sized + Convert,
 {
@@ -122,6 +319,51 @@ where
     fn drop(&mut self) {
         if self.is_owned() {
             unsafe { T::make_owned(self.ptr, self.extent) };
+        } else if self.is_shared() {
+            unsafe { Self::release_shared(self.ptr, self.extent) };
+        }
+    }
+}
+
+impl<T> Deref for CowMut<'_, T>
+where
+    T: ?Sized + Convert,
+{
+    type Target = T::Owned;
+
+    #[inline]
+    fn deref(&self) -> &T::Owned {
+        &self.owned
+    }
+}
+
+impl<T> DerefMut for CowMut<'_, T>
+where
+    T: ?Sized + Convert,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T::Owned {
+        &mut self.owned
+    }
+}
+
+impl<T> Drop for CowMut<'_, T>
+where
+    T: ?Sized + Convert,
+{
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The owned data is never used again.
+        let o = unsafe { ManuallyDrop::take(&mut self.owned) };
+        // Panicking here would abort if the guard is dropped while unwinding,
+        // so data that cannot be stored is dropped instead and the `Cow` is
+        // left empty.
+        match T::try_unmake_owned(o) {
+            Ok((ptr, extent)) => {
+                self.cow.ptr = ptr;
+                self.cow.extent = extent;
+            }
+            Err(_) => self.cow.set_empty(),
         }
     }
 }
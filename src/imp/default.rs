@@ -1,17 +1,37 @@
-//! The standard library [`Cow`] implementation.
+//! A `Cow` implementation with the same layout as the standard library one.
 
-pub use alloc::borrow::Cow;
+use core::borrow::Borrow;
+use core::ptr::NonNull;
+
+use alloc::borrow::Cow as StdCow;
 use alloc::borrow::ToOwned;
 
 use crate::imp;
 
-impl<'a, T> imp::Cow<'a, T> for Cow<'a, T>
+/// A clone-on-write smart pointer that works for any [`ToOwned`] type.
+///
+/// This is the same as the standard library [`Cow`][StdCow] except that the
+/// borrowed variant is a pointer instead of a reference.
+pub enum Cow<T>
+where
+    T: ?Sized + ToOwned,
+{
+    Borrowed(NonNull<T>),
+    Owned(T::Owned),
+}
+
+impl<T> imp::Cow<T> for Cow<T>
 where
     T: ?Sized + ToOwned,
 {
+    type Mut<'c>
+        = &'c mut T::Owned
+    where
+        Self: 'c;
+
     #[inline]
-    fn borrowed(b: &'a T) -> Self {
-        Self::Borrowed(b)
+    fn borrowed(b: &T) -> Self {
+        Self::Borrowed(NonNull::from(b))
     }
 
     #[inline]
@@ -19,6 +39,11 @@ where
         Self::Owned(o)
     }
 
+    #[inline]
+    fn try_owned(o: T::Owned) -> Result<Self, T::Owned> {
+        Ok(Self::Owned(o))
+    }
+
     #[inline]
     fn is_borrowed(&self) -> bool {
         matches!(*self, Self::Borrowed(_))
@@ -29,14 +54,58 @@ where
         matches!(*self, Self::Owned(_))
     }
 
+    #[inline]
+    fn is_shared(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn make_ref(&self) -> &T {
-        &*self
+        match *self {
+            // SAFETY: Borrowed data outlives the `Cow`.
+            Self::Borrowed(b) => unsafe { b.as_ref() },
+            Self::Owned(ref o) => o.borrow(),
+        }
     }
 
     #[inline]
     fn into_owned(self) -> T::Owned {
-        self.into_owned()
+        match self {
+            Self::Borrowed(_) => self.make_ref().to_owned(),
+            Self::Owned(o) => o,
+        }
+    }
+
+    #[inline]
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T> {
+        match *self {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Self::Borrowed(b) => Some(unsafe { b.as_ref() }),
+            Self::Owned(_) => None,
+        }
+    }
+
+    #[inline]
+    unsafe fn into_std<'a>(self) -> StdCow<'a, T> {
+        match self {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Self::Borrowed(b) => StdCow::Borrowed(unsafe { b.as_ref() }),
+            Self::Owned(o) => StdCow::Owned(o),
+        }
+    }
+
+    #[inline]
+    fn share(&mut self) {}
+
+    #[inline]
+    fn to_mut(&mut self) -> &mut T::Owned {
+        if let Self::Borrowed(_) = *self {
+            *self = Self::Owned(self.make_ref().to_owned());
+        }
+        match self {
+            Self::Borrowed(_) => unreachable!(),
+            Self::Owned(o) => o,
+        }
     }
 
     #[inline]
@@ -44,13 +113,28 @@ where
     where
         F: FnOnce(&mut T::Owned),
     {
-        match self {
-            Self::Borrowed(b) => {
-                let mut o = b.to_owned();
-                f(&mut o);
-                *self = Self::Owned(o);
-            }
-            Self::Owned(ref mut o) => f(o),
+        f(self.to_mut())
+    }
+
+    #[inline]
+    fn checked_apply<F>(&mut self, f: F) -> Result<(), T::Owned>
+    where
+        F: FnOnce(&mut T::Owned),
+    {
+        self.apply(f);
+        Ok(())
+    }
+}
+
+impl<T> Clone for Cow<T>
+where
+    T: ?Sized + ToOwned,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        match *self {
+            Self::Borrowed(b) => Self::Borrowed(b),
+            Self::Owned(ref o) => Self::Owned(o.borrow().to_owned()),
         }
     }
 }
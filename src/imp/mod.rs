@@ -2,63 +2,186 @@
 
 mod compact;
 mod default;
-mod sealed;
+mod tagged;
 
+use core::ops::DerefMut;
+
+use alloc::borrow::Cow as StdCow;
 use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::ToBoxed;
+
+pub use self::compact::inline::{Cow as InlineCow, CAPACITY as INLINE_CAPACITY};
+pub use self::compact::{
+    BoxCow as CompactBoxCow, Convert, Cow as CompactCow, Extent, IsOwned, Parts,
+};
+pub use self::default::Cow as DefaultCow;
+pub use self::tagged::Cow as TaggedCow;
 
 /// Defines a `Cow` implementation.
 ///
-/// This trait allows us to provide multiple `Cow` implementations.
-pub trait Cow<'a, T>: Clone
+/// This trait allows us to provide multiple `Cow` implementations. The
+/// implementations do not have a lifetime, instead the public types keep track
+/// of it, which means they are covariant in it.
+pub trait Cow<T>: Clone
 where
     T: ?Sized + ToOwned,
 {
-    fn borrowed(b: &'a T) -> Self;
+    type Mut<'c>: DerefMut<Target = T::Owned>
+    where
+        Self: 'c;
+
+    fn borrowed(b: &T) -> Self;
     fn owned(o: T::Owned) -> Self;
+    fn try_owned(o: T::Owned) -> Result<Self, T::Owned>;
     fn is_borrowed(&self) -> bool;
     fn is_owned(&self) -> bool;
+    fn is_shared(&self) -> bool;
     fn make_ref(&self) -> &T;
     fn into_owned(self) -> T::Owned;
+
+    /// # Safety
+    ///
+    /// The lifetime `'a` must not outlive the data this was borrowed from.
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T>;
+
+    /// # Safety
+    ///
+    /// The lifetime `'a` must not outlive the data this was borrowed from.
+    unsafe fn into_std<'a>(self) -> StdCow<'a, T>;
+
+    fn share(&mut self);
+    fn to_mut(&mut self) -> Self::Mut<'_>;
     fn apply<F: FnOnce(&mut T::Owned)>(&mut self, f: F);
+    fn checked_apply<F: FnOnce(&mut T::Owned)>(&mut self, f: F) -> Result<(), T::Owned>;
+}
+
+/// Defines a `BoxCow` implementation.
+pub trait BoxCow<T>: Clone
+where
+    T: ?Sized + ToBoxed,
+{
+    fn borrowed(b: &T) -> Self;
+    fn owned(o: Box<T>) -> Self;
+    fn is_borrowed(&self) -> bool;
+    fn is_owned(&self) -> bool;
+    fn make_ref(&self) -> &T;
+    fn into_boxed(self) -> Box<T>;
+
+    /// # Safety
+    ///
+    /// The lifetime `'a` must not outlive the data this was borrowed from.
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T>;
 }
 
-/// Internal trait which allows us to have different [`Cow`](crate::Cow)
-/// implementations for the same type across different platforms.
+/// Selects the underlying [`Cow`](crate::Cow) implementation for a type.
+///
+/// This can be implemented for types outside of this crate, either using the
+/// compact implementation by also implementing
+/// [`Convert`](crate::repr::Convert), or using the [`Std`](crate::repr::Std)
+/// implementation. See the [`repr`](crate::repr) module for more details.
 ///
-/// This is a *sealed* trait so only this crate can implement it.
-pub trait Dairy<'a>: ToOwned + sealed::Sealed {
-    type Cow: Cow<'a, Self>;
+/// This is implemented for the primitive types, [`String`], [`Vec<T>`],
+/// [`Box<T>`], and [`Duration`][core::time::Duration] using an implementation
+/// that is one word wide. Other [`Sized`] types that implement [`Clone`] and
+/// have an alignment of at least two can use the same implementation by
+/// choosing [`Tagged`](crate::repr::Tagged). Types with an alignment of one,
+/// like [`u8`], [`bool`], and arrays, use the [`Std`](crate::repr::Std)
+/// implementation.
+pub trait Dairy: ToOwned {
+    type Cow: Cow<Self>;
 }
 
-impl<'a> Dairy<'a> for str {
-    type Cow = compact::Cow<'a, Self>;
+impl Dairy for str {
+    type Cow = compact::Cow<Self>;
 }
 
-impl<'a, T: 'a + Clone> Dairy<'a> for [T] {
-    type Cow = compact::Cow<'a, Self>;
+macro_rules! impl_dairy {
+    ($Cow:ident: $($Ty:ty),+ $(,)?) => {
+        $(
+            impl Dairy for $Ty {
+                type Cow = $Cow<Self>;
+            }
+        )+
+    };
+}
+
+impl_dairy! {
+    TaggedCow:
+    (), char, f32, f64,
+    u16, u32, u64, u128, usize,
+    i16, i32, i64, i128, isize,
+    String, core::time::Duration,
+}
+
+// The tagged implementation needs the lowest bit of a borrowed pointer.
+impl_dairy! { DefaultCow: bool, u8, i8 }
+
+impl<T: Clone> Dairy for Vec<T> {
+    type Cow = tagged::Cow<Self>;
+}
+
+impl<T: ?Sized> Dairy for Box<T>
+where
+    Box<T>: Clone,
+{
+    type Cow = tagged::Cow<Self>;
+}
+
+impl<T: Clone, const N: usize> Dairy for [T; N] {
+    type Cow = DefaultCow<Self>;
+}
+
+impl<T: Clone> Dairy for [T] {
+    type Cow = compact::Cow<Self>;
 }
 
 #[cfg(feature = "std")]
-impl<'a> Dairy<'a> for std::ffi::CStr {
-    type Cow = compact::Cow<'a, Self>;
+impl Dairy for std::ffi::CStr {
+    type Cow = compact::Cow<Self>;
 }
 
-#[cfg(all(feature = "std", os_str_ext))]
-impl<'a> Dairy<'a> for std::ffi::OsStr {
-    type Cow = compact::Cow<'a, Self>;
+#[cfg(feature = "std")]
+impl Dairy for std::ffi::OsStr {
+    type Cow = compact::Cow<Self>;
 }
 
-#[cfg(all(feature = "std", not(os_str_ext)))]
-impl<'a> Dairy<'a> for std::ffi::OsStr {
-    type Cow = default::Cow<'a, Self>;
+#[cfg(feature = "std")]
+impl Dairy for std::path::Path {
+    type Cow = compact::Cow<Self>;
 }
 
-#[cfg(all(feature = "std", os_str_ext))]
-impl<'a> Dairy<'a> for std::path::Path {
-    type Cow = compact::Cow<'a, Self>;
+/// Selects the underlying [`BoxCow`](crate::BoxCow) implementation for a type.
+///
+/// This can be implemented for types outside of this crate that implement
+/// [`Convert`](crate::repr::Convert) using [`Extent`](crate::repr::Extent). See
+/// the [`repr`](crate::repr) module for more details.
+pub trait DairyBox: Dairy + ToBoxed {
+    type BoxCow: BoxCow<Self>;
 }
 
-#[cfg(all(feature = "std", not(os_str_ext)))]
-impl<'a> Dairy<'a> for std::path::Path {
-    type Cow = default::Cow<'a, Self>;
+impl DairyBox for str {
+    type BoxCow = compact::BoxCow<Self>;
+}
+
+impl<T: Clone> DairyBox for [T] {
+    type BoxCow = compact::BoxCow<Self>;
+}
+
+#[cfg(feature = "std")]
+impl DairyBox for std::ffi::CStr {
+    type BoxCow = compact::BoxCow<Self>;
+}
+
+#[cfg(feature = "std")]
+impl DairyBox for std::ffi::OsStr {
+    type BoxCow = compact::BoxCow<Self>;
+}
+
+#[cfg(feature = "std")]
+impl DairyBox for std::path::Path {
+    type BoxCow = compact::BoxCow<Self>;
 }
@@ -0,0 +1,252 @@
+//! A one word `Cow` implementation for sized types.
+//!
+//! Owned data is stored on the heap in an allocation that is aligned to at
+//! least two bytes, which means the lowest bit of the pointer is free to store
+//! the ownership tag. Borrowed data is stored as is, so this is only supported
+//! for types with an alignment of at least two, or zero-sized types.
+
+use core::alloc::Layout;
+use core::marker::PhantomData;
+use core::mem;
+use core::mem::ManuallyDrop;
+use core::ptr;
+use core::ptr::NonNull;
+
+use alloc::alloc::{alloc, dealloc, handle_alloc_error};
+use alloc::borrow::Cow as StdCow;
+
+use crate::imp;
+use crate::imp::Cow as _;
+
+/// Set if the data is owned.
+const OWNED: usize = 1;
+
+/// A clone-on-write smart pointer that is one word wide.
+///
+/// The only niche is the non-null pointer, so unlike the compact `Cow` nesting
+/// `Option` increases the size.
+pub struct Cow<T>
+where
+    T: Clone,
+{
+    /// Pointer to the data. The lowest bit is the ownership tag.
+    ptr: NonNull<u8>,
+
+    /// For drop check, owned data is dropped with the `Cow`.
+    marker: PhantomData<T>,
+}
+
+impl<T> Cow<T>
+where
+    T: Clone,
+{
+    /// Fails to compile if the address of borrowed data might not leave the
+    /// lowest bit free.
+    const ASSERT_ALIGN: () = assert!(
+        mem::align_of::<T>() >= 2 || mem::size_of::<T>() == 0,
+        "the tagged `Cow` requires an alignment of at least two"
+    );
+
+    /// The layout of the heap allocation for owned data.
+    #[inline]
+    fn layout() -> Layout {
+        match Layout::new::<T>().align_to(2) {
+            Ok(layout) => layout,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Returns a well aligned pointer for a zero-sized type.
+    #[inline]
+    fn dangling() -> NonNull<u8> {
+        // SAFETY: The alignment is never zero.
+        unsafe { NonNull::new_unchecked(Self::layout().align() as *mut u8) }
+    }
+
+    #[inline]
+    fn from_ptr(ptr: *mut u8) -> Self {
+        Self {
+            // SAFETY: The pointer is either non-null or tagged.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the untagged pointer to the data.
+    #[inline]
+    fn make_ptr(&self) -> *mut T {
+        let ptr = self.ptr.as_ptr();
+        if self.is_owned() {
+            ptr.wrapping_sub(OWNED) as *mut T
+        } else {
+            ptr as *mut T
+        }
+    }
+
+    /// Takes the owned data out of the heap allocation and frees it.
+    ///
+    /// # Safety
+    ///
+    /// The data must be owned and `self` must not be used afterwards.
+    #[inline]
+    unsafe fn take_owned(&self) -> T {
+        let ptr = self.make_ptr();
+        // SAFETY: The data was written to this allocation in `owned`.
+        let o = unsafe { ptr::read(ptr) };
+        if mem::size_of::<T>() != 0 {
+            unsafe { dealloc(ptr as *mut u8, Self::layout()) };
+        }
+        o
+    }
+}
+
+impl<T> imp::Cow<T> for Cow<T>
+where
+    T: Clone,
+{
+    type Mut<'c>
+        = &'c mut T
+    where
+        Self: 'c;
+
+    #[inline]
+    fn borrowed(b: &T) -> Self {
+        let () = Self::ASSERT_ALIGN;
+        if mem::size_of::<T>() == 0 {
+            Self::from_ptr(Self::dangling().as_ptr())
+        } else {
+            Self::from_ptr(b as *const T as *mut u8)
+        }
+    }
+
+    #[inline]
+    fn owned(o: T) -> Self {
+        let ptr = if mem::size_of::<T>() == 0 {
+            mem::forget(o);
+            Self::dangling().as_ptr()
+        } else {
+            let layout = Self::layout();
+            // SAFETY: The layout has a non-zero size.
+            let ptr = unsafe { alloc(layout) };
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            // SAFETY: The allocation is valid for writes of `T`.
+            unsafe { ptr::write(ptr as *mut T, o) };
+            ptr
+        };
+        Self::from_ptr(ptr.wrapping_add(OWNED))
+    }
+
+    #[inline]
+    fn try_owned(o: T) -> Result<Self, T> {
+        Ok(Self::owned(o))
+    }
+
+    #[inline]
+    fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+
+    #[inline]
+    fn is_owned(&self) -> bool {
+        self.ptr.as_ptr() as usize & OWNED != 0
+    }
+
+    #[inline]
+    fn is_shared(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn make_ref(&self) -> &T {
+        // SAFETY: This is valid for both owned and borrowed variants.
+        unsafe { &*self.make_ptr() }
+    }
+
+    #[inline]
+    fn into_owned(self) -> T {
+        if self.is_owned() {
+            let cow = ManuallyDrop::new(self);
+            // SAFETY: The data is owned and `cow` is not dropped.
+            unsafe { cow.take_owned() }
+        } else {
+            self.make_ref().clone()
+        }
+    }
+
+    #[inline]
+    unsafe fn as_borrowed<'a>(&self) -> Option<&'a T> {
+        if self.is_borrowed() {
+            // SAFETY: The caller guarantees that the data outlives `'a`.
+            Some(unsafe { &*self.make_ptr() })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn into_std<'a>(self) -> StdCow<'a, T> {
+        // SAFETY: The caller guarantees that the data outlives `'a`.
+        match unsafe { self.as_borrowed() } {
+            Some(b) => StdCow::Borrowed(b),
+            None => StdCow::Owned(self.into_owned()),
+        }
+    }
+
+    #[inline]
+    fn share(&mut self) {}
+
+    #[inline]
+    fn to_mut(&mut self) -> &mut T {
+        if self.is_borrowed() {
+            *self = Self::owned(self.make_ref().clone());
+        }
+        // SAFETY: The data is owned.
+        unsafe { &mut *self.make_ptr() }
+    }
+
+    #[inline]
+    fn apply<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        f(self.to_mut())
+    }
+
+    #[inline]
+    fn checked_apply<F>(&mut self, f: F) -> Result<(), T>
+    where
+        F: FnOnce(&mut T),
+    {
+        self.apply(f);
+        Ok(())
+    }
+}
+
+impl<T> Clone for Cow<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        if self.is_owned() {
+            imp::Cow::owned(self.make_ref().clone())
+        } else {
+            imp::Cow::borrowed(self.make_ref())
+        }
+    }
+}
+
+impl<T> Drop for Cow<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn drop(&mut self) {
+        if self.is_owned() {
+            // SAFETY: The data is owned and `self` is not used afterwards.
+            drop(unsafe { self.take_owned() });
+        }
+    }
+}
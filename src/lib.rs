@@ -10,35 +10,62 @@
 //! [`std::borrow::Cow`]. Depending on the platform and type this crate provides
 //! a better underlying implementation which will be more compact. This crate
 //! currently supports the following types: [`str`], [`[T]`][slice], [`CStr`],
-//! [`OsStr`], and [`Path`].
+//! [`OsStr`], [`Path`], and common [`Sized`] types like the primitives,
+//! [`String`][alloc::string::String], and [`Vec<T>`][alloc::vec::Vec]. Other
+//! types can be supported by implementing the [`Dairy`] trait, see the
+//! [`repr`] module, or for transparent newtypes using the [`transparent_cow!`]
+//! macro.
 //!
 //! [`dairy::Cow`][Cow] is also able to provide many more [`From`]
 //! implementations; some which are not possible for the standard library to
 //! provide due to the `alloc`, `std` split. For example `Cow<Path>` now has
 //! the useful `From<&str>` implementation.
 //!
+//! Borrowed `Cow`s of [`str`], [`[T]`][slice], and [`CStr`] can also be
+//! constructed in a constant context using [`Cow::const_str`],
+//! [`Cow::const_slice`], [`Cow::const_c_str`], or the [`cow!`] macro.
+//!
 //! ### Underlying implementation
 //!
 //! - On 64-bit platforms the *compact* implementation of [`Cow`] is two words
 //!   wide, storing the length, capacity, and the ownership tag in the same
-//!   word.
+//!   word. Owned data with a length that does not fit in 32 bits or a capacity
+//!   that does not fit in 29 bits stores the capacity with 16 significant
+//!   bits, shrinking the allocation slightly if needed. Only data with a length
+//!   that does not fit in 40 bits is shrunk to its length.
 //! - On 32-bit platforms the *compact* implementation of [`Cow`] is three words
-//!   wide, storing the capacity and the ownership tag in the same word.
-//! - The **default** implementation simply uses the the standard library
-//!   implementation which is four words wide. This is typically required in
-//!   cases where the standard library does not provide an `.into_raw_parts()`
-//!   or equivalent method for the owned version of types.
+//!   wide, storing the ownership tag in the highest bits of the length and
+//!   capacity.
+//! - The **default** implementation has the same layout as the standard
+//!   library implementation, so it is the same size as [`std::borrow::Cow`].
+//!   This is used for [`Sized`] types with an alignment of one, because the
+//!   *tagged* implementation needs the lowest bit of a borrowed pointer to be
+//!   free. It is also available for other types as [`repr::Std`].
+//! - The *tagged* implementation for [`Sized`] types is one word wide, storing
+//!   owned data on the heap and the ownership tag in the lowest bit of the
+//!   pointer. This requires an alignment of at least two.
+//!
+//! All of the unsized types supported by this crate use the *compact*
+//! implementation on every platform. The [`Sized`] types supported by this
+//! crate use the *tagged* implementation, except for types with an alignment
+//! of one like [`u8`], [`bool`], and arrays, which use the *default*
+//! implementation.
+//!
+//! [`SmallCow`] is an alternative to `Cow<str>` of the same size that stores
+//! short owned strings inline instead of allocating.
 //!
-//! The following table documents how `Cow<T>` is implemented for each type on
-//! depending on the platform.
+//! [`BoxCow`] is an alternative to `Cow` whose owned data is a [`Box`], which
+//! means it only needs to store the length and is two words wide on every
+//! platform.
 //!
-//! | `Cow<T>`     | Unix/WASI | Other          |
-//! | ------------ | --------- | -------------- |
-//! | `Cow<str>`   | *compact* | *compact*      |
-//! | `Cow<[T]>`   | *compact* | *compact*      |
-//! | `Cow<CStr>`  | *compact* | *compact*      |
-//! | `Cow<OsStr>` | *compact* | **default**    |
-//! | `Cow<Path>`  | *compact* | **default**    |
+//! The *compact* implementation and [`SmallCow`] only use a subset of the
+//! values of the byte that stores the ownership tag, which leaves plenty of
+//! niches for enums. For example `Option<Option<Cow<str>>>` is the same size as
+//! `Cow<str>`. The same is true for [`BoxCow`] on 64-bit platforms. The
+//! *tagged* implementation, and [`BoxCow`] on 32-bit platforms, only have the
+//! single niche of their non-null pointer, so `Option` does not increase their
+//! size but nesting it further does. The *default* implementation has the same
+//! niches as [`std::borrow::Cow`].
 
 #![no_std]
 #![warn(unsafe_op_in_unsafe_fn)]
@@ -49,21 +76,32 @@ extern crate alloc;
 extern crate std;
 
 mod as_ref;
+mod box_cow;
 mod cmp;
+mod constant;
+mod cow_mut;
+mod error;
 mod extend;
 mod from;
 mod from_iter;
 mod from_str;
 mod imp;
+mod ownership;
+pub mod repr;
 mod serde;
+mod slicing;
+mod small_cow;
+mod string;
 mod to_boxed;
+mod transparent;
 
 use core::borrow::Borrow;
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::marker::Unpin;
+use core::marker::{PhantomData, Unpin};
 use core::ops::Deref;
 
+use alloc::borrow::Cow as StdCow;
 use alloc::boxed::Box;
 
 #[cfg(feature = "std")]
@@ -72,10 +110,29 @@ use std::{
     path::Path,
 };
 
+pub use crate::box_cow::BoxCow;
+pub use crate::cow_mut::CowMut;
+pub use crate::error::CapacityError;
 use crate::imp::Cow as _;
-pub use crate::imp::Dairy;
+pub use crate::imp::{Dairy, DairyBox};
+pub use crate::ownership::Ownership;
+pub use crate::small_cow::SmallCow;
 pub use crate::to_boxed::ToBoxed;
 
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    #[cfg(target_has_atomic = "ptr")]
+    pub use alloc::sync::Arc;
+    pub use core::borrow::Borrow;
+
+    #[cfg(feature = "serde")]
+    pub use ::serde::de::Error as DeError;
+    #[cfg(feature = "serde")]
+    pub use ::serde::Deserializer;
+}
+
 /// Convenient type alias for a clone-on-write [`str`].
 pub type String<'a> = Cow<'a, str>;
 
@@ -102,16 +159,22 @@ pub type PathBuf<'a> = Cow<'a, Path>;
 ///
 /// `Cow` implements [`Deref`], which means that you can call non-mutating
 /// methods directly on the data it encloses.
+///
+/// Like the standard library [`Cow`][StdCow], it is covariant in its lifetime,
+/// so a `Cow<'static, T>` can be used where a `Cow<'a, T>` is expected.
 pub struct Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     inner: T::Cow,
+
+    /// For the lifetime, this makes `Cow` covariant in it.
+    marker: PhantomData<&'a T>,
 }
 
 impl<'a, T> Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     /// Construct from borrowed data.
     ///
@@ -125,6 +188,7 @@ where
     pub fn borrowed(b: &'a T) -> Self {
         Self {
             inner: T::Cow::borrowed(b),
+            marker: PhantomData,
         }
     }
 
@@ -140,6 +204,31 @@ where
     pub fn owned(o: T::Owned) -> Self {
         Self {
             inner: T::Cow::owned(o),
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct from owned data, failing if it is too large to be stored.
+    ///
+    /// [`Cow::owned`] panics in this case. This only happens when the length
+    /// of the data cannot be represented by the compact implementation, for
+    /// example a slice of zero-sized types with more than 2<sup>61</sup>
+    /// elements on 64-bit platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    /// let cow: Cow<str> = Cow::try_owned(String::from("moo")).unwrap();
+    /// ```
+    #[inline]
+    pub fn try_owned(o: T::Owned) -> Result<Self, CapacityError<T::Owned>> {
+        match T::Cow::try_owned(o) {
+            Ok(inner) => Ok(Self {
+                inner,
+                marker: PhantomData,
+            }),
+            Err(o) => Err(CapacityError::new(o)),
         }
     }
 
@@ -179,11 +268,124 @@ where
         self.inner.is_owned()
     }
 
+    /// Returns true if the data is shared.
+    ///
+    /// See [`.share()`][Cow::share] for more details.
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        self.inner.is_shared()
+    }
+
+    /// Converts owned data into reference counted shared data.
+    ///
+    /// After this cloning the `Cow` only increments a reference count instead
+    /// of cloning the data. Converting to shared data clones the data once, as
+    /// does any later mutation or conversion into owned data. Borrowed data is
+    /// left as is.
+    ///
+    /// This is a noop for types using the *default* or *tagged*
+    /// implementation, for types that do not support sharing, and on targets
+    /// without atomic pointers where [`Arc`][alloc::sync::Arc] is not
+    /// available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let mut cow: Cow<str> = Cow::owned(String::from("moo"));
+    /// cow.share();
+    /// assert!(cow.is_shared());
+    ///
+    /// let clone = cow.clone();
+    /// assert!(clone.is_shared());
+    /// assert_eq!(clone.as_ptr(), cow.as_ptr());
+    /// ```
+    #[inline]
+    pub fn share(&mut self) {
+        self.inner.share()
+    }
+
     #[inline]
     fn make_ref(&self) -> &T {
         self.inner.make_ref()
     }
 
+    /// Returns the borrowed data if the data is borrowed.
+    ///
+    /// Unlike dereferencing, the returned reference has the lifetime of the
+    /// original borrow and not of the `Cow` itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let input = String::from("moo moo");
+    /// let s: &str = {
+    ///     let cow: Cow<str> = Cow::borrowed(&input);
+    ///     cow.as_borrowed().unwrap()
+    /// };
+    /// assert_eq!(s, "moo moo");
+    ///
+    /// let cow: Cow<str> = Cow::owned(String::from("moo"));
+    /// assert_eq!(cow.as_borrowed(), None);
+    /// ```
+    #[inline]
+    pub fn as_borrowed(&self) -> Option<&'a T> {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        unsafe { self.inner.as_borrowed() }
+    }
+
+    /// Returns a borrowed `Cow` of the data without cloning it.
+    ///
+    /// This reborrows the data for the lifetime of `self` regardless of
+    /// whether it is borrowed, owned, or shared. It is the same as
+    /// `Cow::borrowed(&*cow)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// fn moo(cow: Cow<str>) -> bool {
+    ///     cow == "moo"
+    /// }
+    ///
+    /// let cow: Cow<str> = Cow::owned(String::from("moo"));
+    /// assert!(cow.as_cow().is_borrowed());
+    /// assert!(moo(cow.as_cow()));
+    /// assert!(cow.is_owned());
+    /// ```
+    #[inline]
+    pub fn as_cow(&self) -> Cow<'_, T> {
+        Cow::borrowed(self.make_ref())
+    }
+
+    /// Converts into the enclosed borrowed or owned data.
+    ///
+    /// This never clones the data unless it is shared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::{Cow, Ownership};
+    ///
+    /// let cow: Cow<str> = Cow::borrowed("moo");
+    /// assert!(matches!(cow.into_inner(), Ownership::Borrowed("moo")));
+    ///
+    /// let cow: Cow<str> = Cow::owned(String::from("moo"));
+    /// assert!(matches!(cow.into_inner(), Ownership::Owned(s) if s == "moo"));
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> Ownership<'a, T> {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        match unsafe { self.inner.into_std() } {
+            StdCow::Borrowed(b) => Ownership::Borrowed(b),
+            StdCow::Owned(o) => Ownership::Owned(o),
+        }
+    }
+
     /// Converts into owned data.
     ///
     /// Clones the data if it is not already owned.
@@ -243,12 +445,47 @@ where
         T::to_boxed(self.into_owned())
     }
 
+    /// Acquires mutable access to the owned data.
+    ///
+    /// Clones the data if it is not already owned. Unlike the standard library
+    /// this returns a guard instead of a reference, because the compact
+    /// implementation does not store the owned data as is. The owned data is
+    /// written back when the guard is dropped.
+    ///
+    /// If the owned data has grown too large to be stored when the guard is
+    /// dropped, it is dropped and the `Cow` is left empty. This is only
+    /// possible for slices of zero-sized types, use
+    /// [`.checked_apply()`][Cow::checked_apply] to handle it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let mut cow = Cow::borrowed("Moo");
+    /// {
+    ///     let mut s = cow.to_mut();
+    ///     s.make_ascii_uppercase();
+    ///     s.push('!');
+    /// }
+    /// assert_eq!(cow, "MOO!");
+    /// ```
+    #[inline]
+    pub fn to_mut(&mut self) -> CowMut<'_, T> {
+        CowMut::new(self)
+    }
+
     /// Applies the given function to the owned data.
     ///
-    /// Clones the data if it is not already owned. This is useful because the
-    /// compact implementation is not able to provide a `.to_mut()` method like
-    /// the standard library. This function allows you to modify the `Cow`
-    /// without [moving] it.
+    /// Clones the data if it is not already owned. This is equivalent to
+    /// modifying the data through [`.to_mut()`][Cow::to_mut] and allows you
+    /// to modify the `Cow` without [moving] it.
+    ///
+    /// # Panics
+    ///
+    /// If the owned data has grown too large to be stored, see
+    /// [`Cow::try_owned`] for when this can occur. Use
+    /// [`.checked_apply()`][Cow::checked_apply] to handle it.
     ///
     /// # Examples
     ///
@@ -268,11 +505,161 @@ where
     {
         self.inner.apply(f)
     }
+
+    /// Applies the given function to the owned data if the predicate holds.
+    ///
+    /// The predicate is called with the current data and the data is only
+    /// cloned if it returns `true`, so borrowed data stays borrowed when no
+    /// modification is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// fn https(mut cow: Cow<str>) -> Cow<str> {
+    ///     cow.modify_if(|s| !s.starts_with("https://"), |s| s.insert_str(0, "https://"));
+    ///     cow
+    /// }
+    ///
+    /// assert!(https(Cow::borrowed("https://example.com")).is_borrowed());
+    /// assert_eq!(https(Cow::borrowed("example.com")), "https://example.com");
+    /// ```
+    #[inline]
+    pub fn modify_if<P, F>(&mut self, pred: P, f: F)
+    where
+        P: FnOnce(&T) -> bool,
+        F: FnOnce(&mut T::Owned),
+    {
+        if pred(self.make_ref()) {
+            self.apply(f)
+        }
+    }
+
+    /// Maps borrowed data using the given function.
+    ///
+    /// Owned data is returned as is. This is useful for operations that may
+    /// return a subslice of the borrowed data, because the result can borrow
+    /// for the original lifetime `'a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// fn trim(cow: Cow<str>) -> Cow<str> {
+    ///     cow.map_borrowed(|s| Cow::borrowed(s.trim()))
+    /// }
+    ///
+    /// let cow = trim(Cow::borrowed("  moo  "));
+    /// assert!(cow.is_borrowed());
+    /// assert_eq!(cow, "moo");
+    /// ```
+    #[inline]
+    pub fn map_borrowed<F>(self, f: F) -> Self
+    where
+        T: 'a,
+        F: FnOnce(&'a T) -> Self,
+    {
+        match self.as_borrowed() {
+            Some(b) => f(b),
+            None => self,
+        }
+    }
+
+    /// Applies the given function to the owned data and returns its result.
+    ///
+    /// This is the same as [`.apply()`][Cow::apply] except that the closure
+    /// can return a value. Like [`.apply()`][Cow::apply] this panics if the
+    /// owned data has grown too large to be stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let mut cow = Cow::borrowed("Moo!");
+    /// let c = cow.apply_with(|s| s.pop());
+    /// assert_eq!(c, Some('!'));
+    /// assert_eq!(cow, "Moo");
+    /// ```
+    #[inline]
+    pub fn apply_with<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut T::Owned) -> R,
+    {
+        let mut r = None;
+        self.apply(|o| r = Some(f(o)));
+        match r {
+            Some(r) => r,
+            None => unreachable!(),
+        }
+    }
+
+    /// Applies the given fallible function to the owned data.
+    ///
+    /// This is the same as [`.apply()`][Cow::apply] except that the closure
+    /// can return an error. Any modifications made before the error was
+    /// returned are kept. Like [`.apply()`][Cow::apply] this panics if the
+    /// owned data has grown too large to be stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// fn strip(cow: &mut Cow<str>) -> Result<(), &'static str> {
+    ///     cow.try_apply(|s| match s.pop() {
+    ///         Some('!') => Ok(()),
+    ///         _ => Err("expected '!'"),
+    ///     })
+    /// }
+    ///
+    /// let mut cow = Cow::borrowed("Moo!");
+    /// assert_eq!(strip(&mut cow), Ok(()));
+    /// assert_eq!(cow, "Moo");
+    /// assert_eq!(strip(&mut cow), Err("expected '!'"));
+    /// ```
+    #[inline]
+    pub fn try_apply<F, R, E>(&mut self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut T::Owned) -> Result<R, E>,
+    {
+        self.apply_with(f)
+    }
+
+    /// Applies the given function to the owned data, failing if the result is
+    /// too large to be stored.
+    ///
+    /// This is the same as [`.apply()`][Cow::apply] except that it returns an
+    /// error instead of panicking. See [`Cow::try_owned`] for when this can
+    /// occur. If an error is returned then the `Cow` is left empty and the
+    /// owned data is returned in the error.
+    ///
+    /// Not to be confused with [`.try_apply()`][Cow::try_apply], which
+    /// returns the error of the given function instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let mut cow = Cow::borrowed("Moo!");
+    /// cow.checked_apply(|s| s.make_ascii_uppercase()).unwrap();
+    /// assert_eq!(cow, "MOO!");
+    /// ```
+    #[inline]
+    pub fn checked_apply<F>(&mut self, f: F) -> Result<(), CapacityError<T::Owned>>
+    where
+        F: FnOnce(&mut T::Owned),
+    {
+        self.inner.checked_apply(f).map_err(CapacityError::new)
+    }
 }
 
 impl<'a, T> Deref for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     type Target = T;
 
@@ -284,7 +671,7 @@ where
 
 impl<'a, T> Borrow<T> for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     #[inline]
     fn borrow(&self) -> &T {
@@ -294,19 +681,20 @@ where
 
 impl<'a, T> Clone for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
 {
     #[inline]
     fn clone(&self) -> Self {
         Self {
             inner: self.inner.clone(),
+            marker: PhantomData,
         }
     }
 }
 
 impl<'a, T> fmt::Debug for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + fmt::Debug,
+    T: ?Sized + Dairy + fmt::Debug,
     T::Owned: fmt::Debug,
 {
     #[inline]
@@ -317,7 +705,7 @@ where
 
 impl<'a, T> fmt::Display for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + fmt::Display,
+    T: ?Sized + Dairy + fmt::Display,
     T::Owned: fmt::Display,
 {
     #[inline]
@@ -328,7 +716,7 @@ where
 
 impl<'a, T> Default for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
     T::Owned: Default,
 {
     #[inline]
@@ -339,7 +727,7 @@ where
 
 impl<'a, T> Hash for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + Hash,
+    T: ?Sized + Dairy + Hash,
 {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -349,21 +737,21 @@ where
 
 unsafe impl<'a, T> Send for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + Sync,
+    T: ?Sized + Dairy + Sync,
     T::Owned: Send,
 {
 }
 
 unsafe impl<'a, T> Sync for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + Sync,
+    T: ?Sized + Dairy + Sync,
     T::Owned: Sync,
 {
 }
 
 impl<'a, T> Unpin for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
+    T: ?Sized + Dairy,
     T::Owned: Unpin,
 {
 }
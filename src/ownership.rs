@@ -0,0 +1,43 @@
+use core::fmt;
+
+use alloc::borrow::ToOwned;
+
+/// The data enclosed by a [`Cow`][crate::Cow], returned by
+/// [`.into_inner()`][crate::Cow::into_inner].
+pub enum Ownership<'a, T>
+where
+    T: ?Sized + ToOwned,
+{
+    /// Borrowed data.
+    Borrowed(&'a T),
+
+    /// Owned data.
+    Owned(T::Owned),
+}
+
+impl<'a, T> Clone for Ownership<'a, T>
+where
+    T: ?Sized + ToOwned,
+    T::Owned: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(b) => Self::Borrowed(b),
+            Self::Owned(o) => Self::Owned(o.clone()),
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Ownership<'a, T>
+where
+    T: ?Sized + ToOwned + fmt::Debug,
+    T::Owned: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Borrowed(b) => f.debug_tuple("Borrowed").field(b).finish(),
+            Self::Owned(o) => f.debug_tuple("Owned").field(o).finish(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Support for using [`Cow`][crate::Cow] with types outside of this crate.
+//!
+//! The underlying implementation for a type is selected by implementing
+//! [`Dairy`][crate::Dairy]. There are three choices:
+//!
+//! - The [`Compact`] implementation stores a pointer and an [`Extent`]. This
+//!   requires implementing the unsafe [`Convert`] trait which describes how to
+//!   convert the borrowed and owned versions of the type to and from these
+//!   parts. Types that wrap one of the types supported by this crate can simply
+//!   delegate to its implementation. Sharing is only supported if
+//!   [`Convert::try_to_shared`] is implemented.
+//! - The [`Std`] implementation has the same layout as the standard library
+//!   [`Cow`][alloc::borrow::Cow] and works for any
+//!   [`ToOwned`][alloc::borrow::ToOwned] type.
+//! - The [`Tagged`] implementation is one word wide and works for any [`Sized`]
+//!   type that implements [`Clone`] and has an alignment of at least two.
+//!
+//! With the `serde` feature, a [`Cow`][crate::Cow] of a type outside of this
+//! crate can be deserialized by implementing `DeserializeCow`.
+//!
+//! # Examples
+//!
+//! Using the compact implementation for a transparent newtype over [`str`].
+//!
+//! ```
+//! use std::borrow::Borrow;
+//! use std::ptr::NonNull;
+//!
+//! use dairy::repr::{Compact, Convert, Extent, Parts};
+//! use dairy::{Cow, Dairy};
+//!
+//! #[repr(transparent)]
+//! pub struct Name(str);
+//!
+//! #[derive(Clone)]
+//! pub struct NameBuf(String);
+//!
+//! impl Name {
+//!     fn new(s: &str) -> &Self {
+//!         // SAFETY: `Name` is a transparent wrapper around `str`.
+//!         unsafe { &*(s as *const str as *const Self) }
+//!     }
+//! }
+//!
+//! impl Borrow<Name> for NameBuf {
+//!     fn borrow(&self) -> &Name {
+//!         Name::new(&self.0)
+//!     }
+//! }
+//!
+//! impl ToOwned for Name {
+//!     type Owned = NameBuf;
+//!
+//!     fn to_owned(&self) -> NameBuf {
+//!         NameBuf(self.0.to_owned())
+//!     }
+//! }
+//!
+//! unsafe impl Convert for Name {
+//!     type Ptr = u8;
+//!     type Extent = Extent;
+//!
+//!     unsafe fn unmake_ptr(ptr: *const Self) -> Parts<Self> {
+//!         unsafe { str::unmake_ptr(ptr as *const str) }
+//!     }
+//!
+//!     fn try_unmake_owned(o: NameBuf) -> Result<Parts<Self>, NameBuf> {
+//!         str::try_unmake_owned(o.0).map_err(NameBuf)
+//!     }
+//!
+//!     unsafe fn make_ptr(ptr: NonNull<u8>, extent: Extent) -> *const Self {
+//!         unsafe { str::make_ptr(ptr, extent) as *const Self }
+//!     }
+//!
+//!     unsafe fn make_owned(ptr: NonNull<u8>, extent: Extent) -> NameBuf {
+//!         NameBuf(unsafe { str::make_owned(ptr, extent) })
+//!     }
+//! }
+//!
+//! impl Dairy for Name {
+//!     type Cow = Compact<Self>;
+//! }
+//!
+//! let mut cow: Cow<Name> = Cow::owned(NameBuf(String::from("moo")));
+//! assert!(cow.is_owned());
+//! assert_eq!(&cow.0, "moo");
+//!
+//! // `try_to_shared` is not implemented so the data stays owned
+//! cow.share();
+//! assert!(cow.is_owned());
+//! ```
+//!
+//! Using the tagged implementation for a [`Sized`] type.
+//!
+//! ```
+//! use dairy::repr::Tagged;
+//! use dairy::{Cow, Dairy};
+//!
+//! #[derive(Clone)]
+//! pub struct Config {
+//!     name: String,
+//!     verbose: bool,
+//! }
+//!
+//! impl Dairy for Config {
+//!     type Cow = Tagged<Self>;
+//! }
+//!
+//! let config = Config {
+//!     name: String::from("moo"),
+//!     verbose: true,
+//! };
+//! let cow = Cow::borrowed(&config);
+//! assert!(cow.verbose);
+//! ```
+//!
+//! Using the standard library implementation for any other type.
+//!
+//! ```
+//! use dairy::repr::Std;
+//! use dairy::{Cow, Dairy};
+//!
+//! #[derive(Clone)]
+//! pub struct Config {
+//!     verbose: bool,
+//! }
+//!
+//! impl Dairy for Config {
+//!     type Cow = Std<Self>;
+//! }
+//!
+//! let cow = Cow::borrowed(&Config { verbose: true });
+//! assert!(cow.verbose);
+//! ```
+
+pub use crate::imp::{
+    CompactBoxCow as CompactBox, CompactCow as Compact, DefaultCow as Std, TaggedCow as Tagged,
+};
+pub use crate::imp::{Convert, Extent, IsOwned, Parts};
+#[cfg(feature = "serde")]
+pub use crate::serde::DeserializeCow;
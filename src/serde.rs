@@ -1,26 +1,103 @@
 #![cfg(feature = "serde")]
 
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use core::fmt;
+use core::marker::PhantomData;
+use core::str;
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+use std::{
+    ffi::{CStr, CString, OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
 
 use crate::{Cow, Dairy};
 
+/// Deserialize a [`Cow`] of this type.
+///
+/// [`Cow`] implements [`Deserialize`] for any type that implements this trait.
+/// It is implemented separately for each type so that [`Cow<str>`][Cow] can
+/// borrow from the input where possible instead of always allocating.
+///
+/// This is implemented for the types supported by this crate and for newtypes
+/// using [`transparent_cow!`][crate::transparent_cow]. Other types can
+/// implement it, for example using [`Cow::deserialize_owned`] to always
+/// deserialize owned data.
+///
+/// # Examples
+///
+/// ```
+/// # use std::borrow::Borrow;
+/// use dairy::repr::{DeserializeCow, Std};
+/// use dairy::{Cow, Dairy};
+/// use serde::Deserializer;
+/// use serde_derive::Deserialize;
+///
+/// pub struct Flags([bool]);
+///
+/// #[derive(Deserialize)]
+/// pub struct FlagsBuf(Vec<bool>);
+/// #
+/// # impl Borrow<Flags> for FlagsBuf {
+/// #     fn borrow(&self) -> &Flags {
+/// #         unsafe { &*(self.0.as_slice() as *const [bool] as *const Flags) }
+/// #     }
+/// # }
+/// #
+/// # impl ToOwned for Flags {
+/// #     type Owned = FlagsBuf;
+/// #
+/// #     fn to_owned(&self) -> FlagsBuf {
+/// #         FlagsBuf(self.0.to_vec())
+/// #     }
+/// # }
+///
+/// impl Dairy for Flags {
+///     type Cow = Std<Self>;
+/// }
+///
+/// impl<'de, 'a> DeserializeCow<'de, 'a> for Flags {
+///     fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+///     where
+///         D: Deserializer<'de>,
+///     {
+///         Cow::deserialize_owned(deserializer)
+///     }
+/// }
+///
+/// let cow: Cow<Flags> = serde_json::from_str("[true, false]").unwrap();
+/// assert!(cow.0[0]);
+/// ```
+pub trait DeserializeCow<'de, 'a>: Dairy {
+    /// Deserialize a [`Cow`] of this type, borrowing from the input if
+    /// possible.
+    fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
 impl<'de, 'a, T> Deserialize<'de> for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a>,
-    T::Owned: Deserialize<'de>,
+    T: ?Sized + DeserializeCow<'de, 'a>,
 {
     #[inline]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        T::Owned::deserialize(deserializer).map(Cow::owned)
+        T::deserialize_cow(deserializer)
     }
 }
 
 impl<'a, T> Serialize for Cow<'a, T>
 where
-    T: ?Sized + Dairy<'a> + Serialize,
+    T: ?Sized + Dairy + Serialize,
 {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -30,3 +107,265 @@ where
         (**self).serialize(serializer)
     }
 }
+
+impl<'a, T> Cow<'a, T>
+where
+    T: ?Sized + Dairy,
+{
+    /// Deserialize owned data, never borrowing from the input.
+    ///
+    /// [`Cow<str>`][Cow] borrows from the input where possible, so
+    /// `Cow<'static, str>` can only be deserialized from `'static` input and
+    /// does not implement [`DeserializeOwned`][de::DeserializeOwned]. Use this
+    /// function with `#[serde(deserialize_with = "...")]` to deserialize into
+    /// owned data instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_derive::Deserialize;
+    /// use dairy::Cow;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     #[serde(deserialize_with = "Cow::deserialize_owned")]
+    ///     name: Cow<'static, str>,
+    /// }
+    ///
+    /// let config: Config = serde_json::from_reader(&br#"{"name": "moo"}"#[..]).unwrap();
+    /// assert!(config.name.is_owned());
+    /// ```
+    #[inline]
+    pub fn deserialize_owned<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        T::Owned: Deserialize<'de>,
+    {
+        T::Owned::deserialize(deserializer).map(Cow::owned)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Cow<str>
+////////////////////////////////////////////////////////////////////////////////
+
+struct StrVisitor<'a>(PhantomData<Cow<'a, str>>);
+
+impl<'de: 'a, 'a> Visitor<'de> for StrVisitor<'a> {
+    type Value = Cow<'a, str>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::borrowed(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(String::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(v))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(s) => Ok(Cow::borrowed(s)),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match str::from_utf8(v) {
+            Ok(s) => Ok(Cow::owned(String::from(s))),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match String::from_utf8(v) {
+            Ok(s) => Ok(Cow::owned(s)),
+            Err(e) => Err(E::invalid_value(Unexpected::Bytes(e.as_bytes()), &self)),
+        }
+    }
+}
+
+/// Borrows from the input if the deserializer supports it.
+///
+/// Note: this means that `Cow<'static, str>` can only be deserialized from
+/// `'static` input. Use `#[serde(borrow)]` on fields of this type when deriving
+/// [`Deserialize`], or [`Cow::deserialize_owned`] to never borrow.
+impl<'de: 'a, 'a> DeserializeCow<'de, 'a> for str {
+    #[inline]
+    fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Cow<[T]>
+////////////////////////////////////////////////////////////////////////////////
+
+struct BytesVisitor<'a>(PhantomData<Cow<'a, [u8]>>);
+
+impl<'de: 'a, 'a> Visitor<'de> for BytesVisitor<'a> {
+    type Value = Cow<'a, [u8]>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::borrowed(v.as_bytes()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(Vec::from(v.as_bytes())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(v.into_bytes()))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::borrowed(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(Vec::from(v)))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Cow::owned(v))
+    }
+}
+
+impl<'a> Cow<'a, [u8]> {
+    /// Deserialize a byte array, borrowing from the input where possible.
+    ///
+    /// [`Cow<[T]>`][Cow] is deserialized as a sequence just like [`Vec<T>`].
+    /// Use this function with `#[serde(deserialize_with = "...")]` to
+    /// deserialize a byte array without copying instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_derive::Deserialize;
+    /// use dairy::Cow;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Data<'a> {
+    ///     #[serde(borrow, deserialize_with = "Cow::deserialize_bytes")]
+    ///     bytes: Cow<'a, [u8]>,
+    /// }
+    /// ```
+    #[inline]
+    pub fn deserialize_bytes<'de: 'a, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+    }
+}
+
+impl<'de, 'a, T> DeserializeCow<'de, 'a> for [T]
+where
+    T: 'a + Clone,
+    Vec<T>: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(Cow::owned)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Cow<T>
+////////////////////////////////////////////////////////////////////////////////
+
+impl<'de, 'a, T> DeserializeCow<'de, 'a> for T
+where
+    T: 'a + Dairy + ToOwned<Owned = T> + Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Cow::owned)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Cow<CStr>, Cow<OsStr>, Cow<Path>
+////////////////////////////////////////////////////////////////////////////////
+
+macro_rules! impl_owned {
+    ($($Ty:ty => $Owned:ty),+) => {
+        $(
+            #[cfg(feature = "std")]
+            impl<'de, 'a> DeserializeCow<'de, 'a> for $Ty
+            where
+                $Owned: Deserialize<'de>,
+            {
+                #[inline]
+                fn deserialize_cow<D>(deserializer: D) -> Result<Cow<'a, Self>, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    <$Owned>::deserialize(deserializer).map(Cow::owned)
+                }
+            }
+        )+
+    };
+}
+
+impl_owned! {
+    CStr => CString,
+    OsStr => OsString,
+    Path => PathBuf
+}
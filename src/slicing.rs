@@ -0,0 +1,71 @@
+//! Zero-copy slicing for `Cow<[T]>`.
+
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::Cow;
+
+impl<'a, T> Cow<'a, [T]>
+where
+    T: 'a + Clone,
+{
+    /// Returns the given range of the data.
+    ///
+    /// Borrowed data is re-sliced without allocating and keeps the original
+    /// lifetime `'a`. Owned data is truncated in place.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow: Cow<[i32]> = Cow::borrowed(&[1, 2, 3, 4]);
+    /// let cow = cow.slice(1..3);
+    /// assert!(cow.is_borrowed());
+    /// assert_eq!(cow, [2, 3]);
+    /// ```
+    #[inline]
+    pub fn slice<R>(mut self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = to_range(range, self.len());
+        let _ = &self[range.clone()];
+        if let Some(b) = self.as_borrowed() {
+            Self::borrowed(&b[range])
+        } else if range != (0..self.len()) {
+            self.apply(|v| {
+                v.truncate(range.end);
+                v.drain(..range.start);
+            });
+            self
+        } else {
+            self
+        }
+    }
+}
+
+/// Converts any range into a half-open range for data of the given length.
+///
+/// Unbounded ends are filled in, overflow panics, but the range is not checked
+/// against the length; that is left to the subsequent indexing.
+#[inline]
+pub(crate) fn to_range<R>(range: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.checked_add(1).expect("range start out of bounds"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i.checked_add(1).expect("range end out of bounds"),
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    start..end
+}
@@ -0,0 +1,396 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::Infallible;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::borrow::Cow as StdCow;
+use alloc::boxed::Box;
+use alloc::str::FromStr;
+use alloc::string::String;
+
+use crate::imp::{Cow as _, InlineCow, INLINE_CAPACITY};
+use crate::{CapacityError, Cow, CowMut};
+
+/// A clone-on-write string that stores short owned strings inline.
+///
+/// This is the same size as [`Cow<str>`][Cow] but owned strings that are at
+/// most [`SmallCow::INLINE_CAPACITY`] bytes long are stored inline instead of
+/// on the heap, so creating them does not allocate. Borrowed strings are always
+/// stored as a pointer and length regardless of their length.
+///
+/// # Examples
+///
+/// ```
+/// use dairy::SmallCow;
+///
+/// let cow = SmallCow::owned(String::from("id"));
+/// assert!(cow.is_owned());
+/// assert!(cow.is_inline());
+/// assert_eq!(cow, "id");
+/// ```
+pub struct SmallCow<'a> {
+    inner: InlineCow,
+
+    /// For the lifetime, this makes `SmallCow` covariant in it.
+    marker: PhantomData<&'a str>,
+}
+
+impl<'a> SmallCow<'a> {
+    /// The maximum length in bytes of an owned string that is stored inline.
+    ///
+    /// This is 15 on 64-bit platforms and 11 on 32-bit platforms.
+    pub const INLINE_CAPACITY: usize = INLINE_CAPACITY;
+
+    /// Construct from borrowed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::SmallCow;
+    /// let cow = SmallCow::borrowed("moo");
+    /// ```
+    #[inline]
+    pub fn borrowed(b: &'a str) -> Self {
+        Self {
+            inner: InlineCow::borrowed(b),
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct from owned data.
+    ///
+    /// The data is stored inline if it is short enough, in which case the
+    /// given allocation is dropped.
+    ///
+    /// # Panics
+    ///
+    /// See [`Cow::owned`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::SmallCow;
+    /// let cow = SmallCow::owned(String::from("moo"));
+    /// ```
+    #[inline]
+    pub fn owned(o: String) -> Self {
+        Self {
+            inner: InlineCow::owned(o),
+            marker: PhantomData,
+        }
+    }
+
+    /// Construct owned data by copying the given string.
+    ///
+    /// This does not allocate if the string is short enough to be stored
+    /// inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::SmallCow;
+    ///
+    /// let cow = SmallCow::copied("moo");
+    /// assert!(cow.is_owned());
+    /// assert!(cow.is_inline());
+    /// ```
+    #[inline]
+    pub fn copied(s: &str) -> Self {
+        if s.len() <= INLINE_CAPACITY {
+            Self {
+                inner: InlineCow::inline(s),
+                marker: PhantomData,
+            }
+        } else {
+            Self::owned(String::from(s))
+        }
+    }
+
+    /// Construct from owned data, failing if it is too large to be stored.
+    ///
+    /// See [`Cow::try_owned`] for more details.
+    #[inline]
+    pub fn try_owned(o: String) -> Result<Self, CapacityError<String>> {
+        match InlineCow::try_owned(o) {
+            Ok(inner) => Ok(Self {
+                inner,
+                marker: PhantomData,
+            }),
+            Err(o) => Err(CapacityError::new(o)),
+        }
+    }
+
+    /// Returns true if the data is borrowed.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        self.inner.is_borrowed()
+    }
+
+    /// Returns true if the data is owned.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        self.inner.is_owned()
+    }
+
+    /// Returns true if the data is owned and stored inline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::SmallCow;
+    ///
+    /// assert!(SmallCow::owned(String::from("moo")).is_inline());
+    /// assert!(!SmallCow::borrowed("moo").is_inline());
+    /// ```
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.inner.is_inline()
+    }
+
+    /// Returns the borrowed data if it is borrowed.
+    ///
+    /// See [`Cow::as_borrowed`] for more details.
+    #[inline]
+    pub fn as_borrowed(&self) -> Option<&'a str> {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        unsafe { self.inner.as_borrowed() }
+    }
+
+    /// Converts into owned data.
+    ///
+    /// Inline and borrowed data are cloned.
+    #[inline]
+    pub fn into_owned(self) -> String {
+        self.inner.into_owned()
+    }
+
+    /// Converts into boxed data.
+    #[inline]
+    pub fn into_boxed(self) -> Box<str> {
+        self.into_owned().into_boxed_str()
+    }
+
+    /// Returns a guard providing mutable access to the owned data.
+    ///
+    /// Inline data is moved to the heap. Prefer [`.apply()`][SmallCow::apply]
+    /// if the result may be short enough to be stored inline again.
+    #[inline]
+    pub fn to_mut(&mut self) -> CowMut<'_, str> {
+        CowMut::from_inner(self.inner.to_mut())
+    }
+
+    /// Applies the given function to the owned data.
+    ///
+    /// The result is stored inline if it is short enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::SmallCow;
+    ///
+    /// let mut cow = SmallCow::borrowed("moo");
+    /// cow.apply(|s| s.make_ascii_uppercase());
+    /// assert!(cow.is_inline());
+    /// assert_eq!(cow, "MOO");
+    /// ```
+    #[inline]
+    pub fn apply<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut String),
+    {
+        self.inner.apply(f)
+    }
+}
+
+impl Deref for SmallCow<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.inner.make_ref()
+    }
+}
+
+impl Borrow<str> for SmallCow<'_> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.inner.make_ref()
+    }
+}
+
+impl AsRef<str> for SmallCow<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.inner.make_ref()
+    }
+}
+
+impl Clone for SmallCow<'_> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl fmt::Debug for SmallCow<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl fmt::Display for SmallCow<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl Default for SmallCow<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::borrowed("")
+    }
+}
+
+impl Hash for SmallCow<'_> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl PartialEq for SmallCow<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl Eq for SmallCow<'_> {}
+
+impl PartialOrd for SmallCow<'_> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SmallCow<'_> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+impl PartialEq<str> for SmallCow<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        PartialEq::eq(&**self, other)
+    }
+}
+
+impl PartialEq<&str> for SmallCow<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        PartialEq::eq(&**self, *other)
+    }
+}
+
+impl PartialEq<String> for SmallCow<'_> {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+impl FromStr for SmallCow<'_> {
+    type Err = Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::copied(s))
+    }
+}
+
+impl<'a> From<&'a str> for SmallCow<'a> {
+    #[inline]
+    fn from(s: &'a str) -> Self {
+        Self::borrowed(s)
+    }
+}
+
+impl<'a> From<&'a String> for SmallCow<'a> {
+    #[inline]
+    fn from(s: &'a String) -> Self {
+        Self::borrowed(s.as_str())
+    }
+}
+
+impl From<String> for SmallCow<'_> {
+    #[inline]
+    fn from(s: String) -> Self {
+        Self::owned(s)
+    }
+}
+
+impl From<char> for SmallCow<'_> {
+    #[inline]
+    fn from(c: char) -> Self {
+        let mut buf = [0; 4];
+        Self::copied(c.encode_utf8(&mut buf))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for SmallCow<'a> {
+    #[inline]
+    fn from(c: Cow<'a, str>) -> Self {
+        match c.as_borrowed() {
+            Some(b) => Self::borrowed(b),
+            None => Self::owned(c.into_owned()),
+        }
+    }
+}
+
+impl<'a> From<SmallCow<'a>> for Cow<'a, str> {
+    #[inline]
+    fn from(c: SmallCow<'a>) -> Self {
+        match c.as_borrowed() {
+            Some(b) => Self::borrowed(b),
+            None => Self::owned(c.into_owned()),
+        }
+    }
+}
+
+impl<'a> From<StdCow<'a, str>> for SmallCow<'a> {
+    #[inline]
+    fn from(c: StdCow<'a, str>) -> Self {
+        match c {
+            StdCow::Borrowed(b) => Self::borrowed(b),
+            StdCow::Owned(o) => Self::owned(o),
+        }
+    }
+}
+
+impl<'a> From<SmallCow<'a>> for StdCow<'a, str> {
+    #[inline]
+    fn from(c: SmallCow<'a>) -> Self {
+        // SAFETY: Borrowed data is valid for the lifetime `'a`.
+        unsafe { c.inner.into_std() }
+    }
+}
+
+impl From<SmallCow<'_>> for String {
+    #[inline]
+    fn from(c: SmallCow<'_>) -> Self {
+        c.into_owned()
+    }
+}
+
+unsafe impl Send for SmallCow<'_> {}
+
+unsafe impl Sync for SmallCow<'_> {}
@@ -0,0 +1,200 @@
+//! Copy-free string helpers for `Cow<str>`.
+
+use core::ops::{Range, RangeBounds};
+
+use crate::slicing::to_range;
+use crate::Cow;
+
+impl<'a> Cow<'a, str> {
+    /// Returns the data with leading and trailing whitespace removed.
+    ///
+    /// Borrowed data is re-sliced without allocating. Owned data is trimmed in
+    /// place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow = Cow::borrowed("  moo  ").trim_cow();
+    /// assert!(cow.is_borrowed());
+    /// assert_eq!(cow, "moo");
+    /// ```
+    #[inline]
+    pub fn trim_cow(self) -> Self {
+        self.trim_with(str::trim)
+    }
+
+    /// Returns the data with leading whitespace removed.
+    ///
+    /// See [`.trim_cow()`][Cow::trim_cow] for more details.
+    #[inline]
+    pub fn trim_start_cow(self) -> Self {
+        self.trim_with(str::trim_start)
+    }
+
+    /// Returns the data with trailing whitespace removed.
+    ///
+    /// See [`.trim_cow()`][Cow::trim_cow] for more details.
+    #[inline]
+    pub fn trim_end_cow(self) -> Self {
+        self.trim_with(str::trim_end)
+    }
+
+    /// Replaces all matches of a pattern with another string.
+    ///
+    /// Only allocates if the pattern is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow = Cow::borrowed("moo").replace_cow("baa", "moo");
+    /// assert!(cow.is_borrowed());
+    ///
+    /// let cow = Cow::borrowed("moo moo").replace_cow("moo", "baa");
+    /// assert_eq!(cow, "baa baa");
+    /// ```
+    #[inline]
+    pub fn replace_cow(self, from: &str, to: &str) -> Self {
+        if self.contains(from) {
+            Self::owned(self.replace(from, to))
+        } else {
+            self
+        }
+    }
+
+    /// Returns the lowercase equivalent of the data.
+    ///
+    /// Only allocates if any character changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow = Cow::borrowed("moo").to_lowercase_cow();
+    /// assert!(cow.is_borrowed());
+    ///
+    /// let cow = Cow::borrowed("MOO").to_lowercase_cow();
+    /// assert_eq!(cow, "moo");
+    /// ```
+    #[inline]
+    pub fn to_lowercase_cow(self) -> Self {
+        if self.chars().all(|c| c.to_lowercase().eq([c])) {
+            self
+        } else {
+            Self::owned(self.to_lowercase())
+        }
+    }
+
+    /// Returns the uppercase equivalent of the data.
+    ///
+    /// Only allocates if any character changes.
+    #[inline]
+    pub fn to_uppercase_cow(self) -> Self {
+        if self.chars().all(|c| c.to_uppercase().eq([c])) {
+            self
+        } else {
+            Self::owned(self.to_uppercase())
+        }
+    }
+
+    /// Returns the ASCII lowercase equivalent of the data.
+    ///
+    /// Only allocates if the data is borrowed and any character changes. Owned
+    /// data is converted in place.
+    #[inline]
+    pub fn to_ascii_lowercase_cow(mut self) -> Self {
+        if self.bytes().any(|b| b.is_ascii_uppercase()) {
+            self.apply(|s| s.make_ascii_lowercase());
+        }
+        self
+    }
+
+    /// Returns the ASCII uppercase equivalent of the data.
+    ///
+    /// Only allocates if the data is borrowed and any character changes. Owned
+    /// data is converted in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow = Cow::borrowed("MOO").to_ascii_uppercase_cow();
+    /// assert!(cow.is_borrowed());
+    ///
+    /// let cow = Cow::borrowed("moo").to_ascii_uppercase_cow();
+    /// assert_eq!(cow, "MOO");
+    /// ```
+    #[inline]
+    pub fn to_ascii_uppercase_cow(mut self) -> Self {
+        if self.bytes().any(|b| b.is_ascii_lowercase()) {
+            self.apply(|s| s.make_ascii_uppercase());
+        }
+        self
+    }
+
+    /// Returns the given range of the data.
+    ///
+    /// Borrowed data is re-sliced without allocating and keeps the original
+    /// lifetime `'a`. Owned data is truncated in place.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on character boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dairy::Cow;
+    ///
+    /// let cow = Cow::borrowed("Hello World!").slice(6..11);
+    /// assert!(cow.is_borrowed());
+    /// assert_eq!(cow, "World");
+    /// ```
+    #[inline]
+    pub fn slice<R>(self, range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = to_range(range, self.len());
+        let _ = &self[range.clone()];
+        self.retain(range)
+    }
+
+    /// Retains the subslice returned by the given function.
+    #[inline]
+    fn trim_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&str) -> &str,
+    {
+        let range = {
+            let s: &str = &self;
+            let t = f(s);
+            let start = t.as_ptr() as usize - s.as_ptr() as usize;
+            start..start + t.len()
+        };
+        self.retain(range)
+    }
+
+    /// Retains the given range of the data.
+    ///
+    /// The range must be in bounds and lie on character boundaries.
+    #[inline]
+    fn retain(mut self, range: Range<usize>) -> Self {
+        if let Some(b) = self.as_borrowed() {
+            Self::borrowed(&b[range])
+        } else if range != (0..self.len()) {
+            self.apply(|s| {
+                s.truncate(range.end);
+                s.drain(..range.start);
+            });
+            self
+        } else {
+            self
+        }
+    }
+}
@@ -0,0 +1,302 @@
+/// Implements [`Cow`][crate::Cow] support for a transparent newtype.
+///
+/// Given a `#[repr(transparent)]` newtype over [`str`] or [`[T]`][slice] and a
+/// newtype over its owned version, this macro implements the following by
+/// delegating to the implementation for the wrapped type.
+///
+/// - [`ToOwned`][alloc::borrow::ToOwned] for the borrowed type and
+///   [`Borrow`][core::borrow::Borrow] and [`AsRef`] for the owned type.
+/// - [`Convert`][crate::repr::Convert], [`Dairy`][crate::Dairy],
+///   [`DairyBox`][crate::DairyBox], and [`ToBoxed`][crate::ToBoxed] so that
+///   the compact implementation is used.
+/// - [`From`] conversions between the [`Cow`][crate::Cow] and the borrowed,
+///   owned, and boxed types.
+/// - [`PartialEq`] between the [`Cow`][crate::Cow] and the borrowed and owned
+///   types.
+/// - [`Deserialize`](https://docs.rs/serde/1/serde/trait.Deserialize.html)
+///   for the [`Cow`][crate::Cow] if the `serde` feature is enabled and a
+///   validation function is given with `where validate = path`.
+///   [`Serialize`](https://docs.rs/serde/1/serde/trait.Serialize.html) is
+///   implemented if the borrowed type implements it.
+///
+/// The validation function takes a reference to the wrapped data and returns
+/// a `Result<(), E>` where `E` implements [`Display`][core::fmt::Display]. It
+/// is called on deserialized data before it is wrapped and any error is
+/// returned from the deserializer. Without it the newtype could be
+/// constructed from arbitrary data, so no `Deserialize` implementation is
+/// provided.
+///
+/// # Safety
+///
+/// Each invocation must start with `unsafe` because the macro converts
+/// references, boxes, and [`Arc`][alloc::sync::Arc]s of the wrapped type to the
+/// borrowed type by casting pointers. The caller must guarantee that the
+/// borrowed type is a `#[repr(transparent)]` struct whose only field is of the
+/// wrapped type.
+///
+/// # Examples
+///
+/// ```
+/// use dairy::Cow;
+///
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// pub struct NameBuf(String);
+///
+/// // SAFETY: `Name` is a transparent wrapper around `str`.
+/// dairy::transparent_cow!(unsafe Name(str) => NameBuf(String));
+///
+/// let cow: Cow<Name> = Cow::from(NameBuf(String::from("moo")));
+/// assert!(cow.is_owned());
+/// assert_eq!(&cow.0, "moo");
+/// ```
+///
+/// With a validation function for deserialization.
+///
+/// ```
+/// #[repr(transparent)]
+/// pub struct Name(str);
+///
+/// pub struct NameBuf(String);
+///
+/// fn validate(s: &str) -> Result<(), &'static str> {
+///     if s.is_empty() {
+///         Err("name must not be empty")
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// // SAFETY: `Name` is a transparent wrapper around `str`.
+/// dairy::transparent_cow!(unsafe Name(str) => NameBuf(String) where validate = validate);
+/// ```
+#[macro_export]
+macro_rules! transparent_cow {
+    ($(
+        unsafe $Ty:ident($Inner:ty) => $Owned:ident($OwnedInner:ty)
+        $(where validate = $validate:path)?
+    ),+ $(,)?) => {
+        $(
+            impl $crate::__private::ToOwned for $Ty {
+                type Owned = $Owned;
+
+                #[inline]
+                fn to_owned(&self) -> $Owned {
+                    $Owned($crate::__private::ToOwned::to_owned(&self.0))
+                }
+            }
+
+            impl $crate::__private::Borrow<$Ty> for $Owned {
+                #[inline]
+                fn borrow(&self) -> &$Ty {
+                    let b: &$Inner = $crate::__private::Borrow::borrow(&self.0);
+                    // SAFETY: The type is a transparent wrapper.
+                    unsafe { &*(b as *const $Inner as *const $Ty) }
+                }
+            }
+
+            impl ::core::convert::AsRef<$Ty> for $Owned {
+                #[inline]
+                fn as_ref(&self) -> &$Ty {
+                    $crate::__private::Borrow::borrow(self)
+                }
+            }
+
+            unsafe impl $crate::repr::Convert for $Ty {
+                type Ptr = <$Inner as $crate::repr::Convert>::Ptr;
+                type Extent = <$Inner as $crate::repr::Convert>::Extent;
+
+                #[inline]
+                unsafe fn unmake_ptr(ptr: *const Self) -> $crate::repr::Parts<Self> {
+                    // SAFETY: The type is a transparent wrapper.
+                    unsafe { <$Inner as $crate::repr::Convert>::unmake_ptr(ptr as *const $Inner) }
+                }
+
+                #[inline]
+                fn empty() -> $crate::repr::Parts<Self> {
+                    <$Inner as $crate::repr::Convert>::empty()
+                }
+
+                #[inline]
+                fn try_unmake_owned(
+                    o: $Owned,
+                ) -> ::core::result::Result<$crate::repr::Parts<Self>, $Owned> {
+                    <$Inner as $crate::repr::Convert>::try_unmake_owned(o.0).map_err($Owned)
+                }
+
+                #[cfg(target_has_atomic = "ptr")]
+                #[inline]
+                fn try_to_shared(
+                    o: $Owned,
+                ) -> ::core::result::Result<$crate::__private::Arc<Self>, $Owned> {
+                    <$Inner as $crate::repr::Convert>::try_to_shared(o.0)
+                        .map(|a| {
+                            // SAFETY: The type is a transparent wrapper.
+                            unsafe {
+                                $crate::__private::Arc::from_raw(
+                                    $crate::__private::Arc::into_raw(a) as *const Self,
+                                )
+                            }
+                        })
+                        .map_err($Owned)
+                }
+
+                #[inline]
+                unsafe fn make_ptr(
+                    ptr: ::core::ptr::NonNull<Self::Ptr>,
+                    extent: Self::Extent,
+                ) -> *const Self {
+                    let ptr = unsafe { <$Inner as $crate::repr::Convert>::make_ptr(ptr, extent) };
+                    ptr as *const Self
+                }
+
+                #[inline]
+                unsafe fn make_owned(
+                    ptr: ::core::ptr::NonNull<Self::Ptr>,
+                    extent: Self::Extent,
+                ) -> $Owned {
+                    $Owned(unsafe { <$Inner as $crate::repr::Convert>::make_owned(ptr, extent) })
+                }
+            }
+
+            impl $crate::Dairy for $Ty {
+                type Cow = $crate::repr::Compact<Self>;
+            }
+
+            impl $crate::DairyBox for $Ty {
+                type BoxCow = $crate::repr::CompactBox<Self>;
+            }
+
+            impl $crate::ToBoxed for $Ty {
+                #[inline]
+                fn to_boxed(o: $Owned) -> $crate::__private::Box<Self> {
+                    let b = <$Inner as $crate::ToBoxed>::to_boxed(o.0);
+                    // SAFETY: The type is a transparent wrapper.
+                    unsafe {
+                        $crate::__private::Box::from_raw(
+                            $crate::__private::Box::into_raw(b) as *mut Self
+                        )
+                    }
+                }
+            }
+
+            impl<'a> ::core::convert::From<&'a $Ty> for $crate::Cow<'a, $Ty> {
+                #[inline]
+                fn from(b: &'a $Ty) -> Self {
+                    Self::borrowed(b)
+                }
+            }
+
+            impl ::core::convert::From<$Owned> for $crate::Cow<'_, $Ty> {
+                #[inline]
+                fn from(o: $Owned) -> Self {
+                    Self::owned(o)
+                }
+            }
+
+            impl<'a> ::core::convert::From<&'a $Owned> for $crate::Cow<'a, $Ty> {
+                #[inline]
+                fn from(o: &'a $Owned) -> Self {
+                    Self::borrowed($crate::__private::Borrow::borrow(o))
+                }
+            }
+
+            impl ::core::convert::From<$crate::__private::Box<$Ty>> for $crate::Cow<'_, $Ty> {
+                #[inline]
+                fn from(b: $crate::__private::Box<$Ty>) -> Self {
+                    // SAFETY: The type is a transparent wrapper.
+                    let b = unsafe {
+                        $crate::__private::Box::from_raw(
+                            $crate::__private::Box::into_raw(b) as *mut $Inner
+                        )
+                    };
+                    Self::owned($Owned(<$OwnedInner>::from(b)))
+                }
+            }
+
+            impl ::core::convert::From<$crate::Cow<'_, $Ty>> for $Owned {
+                #[inline]
+                fn from(c: $crate::Cow<'_, $Ty>) -> Self {
+                    c.into_owned()
+                }
+            }
+
+            impl ::core::convert::From<$crate::Cow<'_, $Ty>> for $crate::__private::Box<$Ty> {
+                #[inline]
+                fn from(c: $crate::Cow<'_, $Ty>) -> Self {
+                    c.into_boxed()
+                }
+            }
+
+            impl ::core::cmp::PartialEq<$Ty> for $crate::Cow<'_, $Ty> {
+                #[inline]
+                fn eq(&self, other: &$Ty) -> bool {
+                    ::core::cmp::PartialEq::eq(&self.0, &other.0)
+                }
+            }
+
+            impl ::core::cmp::PartialEq<&$Ty> for $crate::Cow<'_, $Ty> {
+                #[inline]
+                fn eq(&self, other: &&$Ty) -> bool {
+                    ::core::cmp::PartialEq::eq(&self.0, &other.0)
+                }
+            }
+
+            impl ::core::cmp::PartialEq<$Owned> for $crate::Cow<'_, $Ty> {
+                #[inline]
+                fn eq(&self, other: &$Owned) -> bool {
+                    ::core::cmp::PartialEq::eq(&self.0, &other.0)
+                }
+            }
+
+            $crate::__transparent_cow_serde!($Ty($Inner) => $Owned $(, $validate)?);
+        )+
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __transparent_cow_serde {
+    ($Ty:ident($Inner:ty) => $Owned:ident) => {};
+    ($Ty:ident($Inner:ty) => $Owned:ident, $validate:path) => {
+        impl<'de: 'a, 'a> $crate::repr::DeserializeCow<'de, 'a> for $Ty {
+            #[inline]
+            fn deserialize_cow<D>(
+                deserializer: D,
+            ) -> ::core::result::Result<$crate::Cow<'a, Self>, D::Error>
+            where
+                D: $crate::__private::Deserializer<'de>,
+            {
+                let c = <$Inner as $crate::repr::DeserializeCow<'de, 'a>>::deserialize_cow(
+                    deserializer,
+                )?;
+                if let ::core::result::Result::Err(e) = $validate(&*c) {
+                    return ::core::result::Result::Err(
+                        <D::Error as $crate::__private::DeError>::custom(e),
+                    );
+                }
+                ::core::result::Result::Ok(match c.as_borrowed() {
+                    // SAFETY: The type is a transparent wrapper.
+                    ::core::option::Option::Some(b) => {
+                        $crate::Cow::borrowed(unsafe { &*(b as *const $Inner as *const $Ty) })
+                    }
+                    ::core::option::Option::None => $crate::Cow::owned($Owned(c.into_owned())),
+                })
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __transparent_cow_serde {
+    ($Ty:ident($Inner:ty) => $Owned:ident $(, $validate:path)?) => {
+        // Avoid an unused function warning when the feature is disabled.
+        $(const _: () = {
+            let _ = $validate;
+        };)?
+    };
+}
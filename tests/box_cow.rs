@@ -0,0 +1,152 @@
+use dairy::{BoxCow, Cow};
+
+#[test]
+fn box_cow_is_borrowed() {
+    let c: BoxCow<str> = BoxCow::borrowed("Hello");
+    assert!(c.is_borrowed());
+    assert!(!c.is_owned());
+    assert_eq!(c.as_borrowed(), Some("Hello"));
+    assert_eq!(c, "Hello");
+}
+
+#[test]
+fn box_cow_is_owned() {
+    let c: BoxCow<str> = BoxCow::owned(Box::from("Hello"));
+    assert!(!c.is_borrowed());
+    assert!(c.is_owned());
+    assert_eq!(c.as_borrowed(), None);
+    assert_eq!(c, "Hello");
+
+    let c: BoxCow<str> = BoxCow::owned(Box::from(""));
+    assert!(c.is_owned());
+    assert_eq!(c, "");
+}
+
+#[test]
+fn box_cow_into_boxed() {
+    let b: Box<str> = Box::from("Hello");
+    let ptr = b.as_ptr();
+    let c = BoxCow::owned(b);
+    let b = c.into_boxed();
+    assert_eq!(b.as_ptr(), ptr);
+
+    let c: BoxCow<str> = BoxCow::borrowed("Hello");
+    assert_eq!(c.into_boxed(), Box::from("Hello"));
+}
+
+#[test]
+fn box_cow_into_owned() {
+    let c: BoxCow<str> = BoxCow::owned(Box::from("Hello"));
+    assert_eq!(c.into_owned(), "Hello");
+
+    let c: BoxCow<[i32]> = BoxCow::borrowed(&[1, 2, 3]);
+    assert_eq!(c.into_owned(), vec![1, 2, 3]);
+}
+
+#[test]
+fn box_cow_clone() {
+    let c: BoxCow<str> = BoxCow::owned(Box::from("Hello"));
+    let d = c.clone();
+    assert!(d.is_owned());
+    assert_ne!(c.as_ptr(), d.as_ptr());
+    assert_eq!(c, d);
+
+    let c: BoxCow<str> = BoxCow::borrowed("Hello");
+    let d = c.clone();
+    assert!(d.is_borrowed());
+    assert_eq!(c.as_ptr(), d.as_ptr());
+}
+
+#[test]
+fn box_cow_from_cow() {
+    let c: BoxCow<str> = BoxCow::from(Cow::borrowed("Hello"));
+    assert!(c.is_borrowed());
+
+    let c: BoxCow<[i32]> = BoxCow::from(Cow::owned(vec![1, 2, 3]));
+    assert!(c.is_owned());
+    assert_eq!(c, [1, 2, 3][..]);
+    assert_eq!(Box::<[i32]>::from(c), Box::from([1, 2, 3]));
+}
+
+#[test]
+fn box_cow_c_str() {
+    use std::ffi::{CStr, CString};
+
+    let s = CString::new("Hello").unwrap().into_boxed_c_str();
+    let c: BoxCow<CStr> = BoxCow::owned(s);
+    assert!(c.is_owned());
+    assert_eq!(c.to_bytes(), b"Hello");
+}
+
+// The tests below construct, read, clone, unwrap, and drop an owned `BoxCow`
+// of each supported type so that Miri checks the whole round trip.
+
+#[test]
+fn box_cow_owned_str() {
+    let b: Box<str> = Box::from("Hello");
+    let ptr = b.as_ptr();
+    let c = BoxCow::owned(b);
+    assert_eq!(c, "Hello");
+    assert_eq!(c.clone(), "Hello");
+    let b = c.into_boxed();
+    assert_eq!(b.as_ptr(), ptr);
+    drop(BoxCow::owned(b));
+}
+
+#[test]
+fn box_cow_owned_slice() {
+    let b: Box<[String]> = vec![String::from("Hello")].into_boxed_slice();
+    let ptr = b.as_ptr();
+    let c = BoxCow::owned(b);
+    assert_eq!(c, [String::from("Hello")][..]);
+    assert_eq!(c.clone(), [String::from("Hello")][..]);
+    let b = c.into_boxed();
+    assert_eq!(b.as_ptr(), ptr);
+    drop(BoxCow::owned(b));
+
+    let c: BoxCow<[()]> = BoxCow::owned(vec![(); 3].into_boxed_slice());
+    assert_eq!(c.clone().len(), 3);
+    assert_eq!(c.into_boxed().len(), 3);
+}
+
+#[test]
+fn box_cow_owned_c_str() {
+    use std::ffi::{CStr, CString};
+
+    let b: Box<CStr> = CString::new("Hello").unwrap().into_boxed_c_str();
+    let ptr = b.as_ptr();
+    let c = BoxCow::owned(b);
+    assert_eq!(c.to_bytes(), b"Hello");
+    assert_eq!(c.clone().to_bytes(), b"Hello");
+    let b = c.into_boxed();
+    assert_eq!(b.as_ptr(), ptr);
+    drop(BoxCow::owned(b));
+}
+
+#[test]
+fn box_cow_owned_os_str() {
+    use std::ffi::{OsStr, OsString};
+
+    let b: Box<OsStr> = OsString::from("Hello").into_boxed_os_str();
+    let ptr = b.as_encoded_bytes().as_ptr();
+    let c = BoxCow::owned(b);
+    assert_eq!(c, OsStr::new("Hello"));
+    assert_eq!(c.clone(), OsStr::new("Hello"));
+    let b = c.into_boxed();
+    assert_eq!(b.as_encoded_bytes().as_ptr(), ptr);
+    drop(BoxCow::owned(b));
+}
+
+#[test]
+fn box_cow_owned_path() {
+    use std::path::{Path, PathBuf};
+
+    let b: Box<Path> = PathBuf::from("/hello").into_boxed_path();
+    let ptr = b.as_os_str().as_encoded_bytes().as_ptr();
+    let c = BoxCow::owned(b);
+    assert_eq!(c, Path::new("/hello"));
+    assert_eq!(c.clone(), Path::new("/hello"));
+    let b = c.into_boxed();
+    assert_eq!(b.as_os_str().as_encoded_bytes().as_ptr(), ptr);
+    drop(BoxCow::owned(b));
+}
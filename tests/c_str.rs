@@ -82,6 +82,18 @@ fn cow_c_str_owned_clone() {
     assert!(c2.is_owned());
 }
 
+#[test]
+fn cow_c_str_share() {
+    let mut c = T::owned(c_string());
+    c.share();
+    assert!(c.is_shared());
+    let c2 = c.clone();
+    assert_eq!(c2.as_ptr(), c.as_ptr());
+    drop(c);
+    assert_eq!(c2, c_str());
+    assert_eq!(c2.into_owned(), c_string());
+}
+
 #[test]
 fn cow_c_str_borrowed_as_ref() {
     let c = T::borrowed(c_str());
@@ -124,3 +136,26 @@ fn cow_c_str_borrowed_partial_eq() {
     assert_eq!(c, &c_string());
     // assert_eq!(c, Box::new(c_str()));
 }
+
+#[test]
+fn cow_c_str_to_mut_forget() {
+    let mut c = T::owned(c_string());
+    let m = std::mem::ManuallyDrop::new(c.to_mut());
+    // Drop the owned data so that leaking the guard does not leak memory.
+    drop(unsafe { std::ptr::read(&**m) });
+    assert!(c.is_borrowed());
+    assert_eq!(c.to_bytes(), b"");
+    assert_eq!(c.to_bytes_with_nul(), b"\0");
+}
+
+#[test]
+fn cow_c_str_apply_panic() {
+    let mut c = T::owned(c_string());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        c.apply(|_| panic!("oops"));
+    }));
+    assert!(result.is_err());
+    assert!(c.is_borrowed());
+    assert_eq!(c.to_bytes(), b"");
+    assert_eq!(c.to_bytes_with_nul(), b"\0");
+}
@@ -0,0 +1,69 @@
+use std::ffi::CStr;
+
+use dairy::Cow;
+
+static NAME: Cow<str> = Cow::const_str("moo");
+
+static DEFAULTS: [Cow<str>; 3] = [dairy::cow!("moo"), dairy::cow!(""), dairy::cow!(LONG)];
+
+const LONG: &str = "a string that is longer than fifteen bytes";
+
+static BYTES: Cow<[u8]> = Cow::const_slice(b"moo");
+
+static EMPTY: Cow<[String]> = Cow::const_slice(&[]);
+
+const MOO: &CStr = match CStr::from_bytes_with_nul(b"moo\0") {
+    Ok(c) => c,
+    Err(_) => panic!("invalid C string"),
+};
+
+static C_STR: Cow<CStr> = Cow::const_c_str(MOO);
+
+#[test]
+fn constant_str() {
+    assert!(NAME.is_borrowed());
+    assert_eq!(NAME, "moo");
+    assert_eq!(NAME.clone().into_owned(), "moo");
+
+    assert!(DEFAULTS.iter().all(Cow::is_borrowed));
+    assert_eq!(DEFAULTS, ["moo", "", LONG]);
+}
+
+#[test]
+fn constant_str_to_mut() {
+    let mut c = NAME.clone();
+    c.to_mut().push_str(" baa");
+    assert!(c.is_owned());
+    assert_eq!(c, "moo baa");
+    assert_eq!(NAME, "moo");
+}
+
+#[test]
+fn constant_str_eq_borrowed() {
+    assert_eq!(NAME, Cow::borrowed("moo"));
+    assert_eq!(DEFAULTS[2], Cow::borrowed(LONG));
+}
+
+#[test]
+fn constant_slice() {
+    assert!(BYTES.is_borrowed());
+    assert_eq!(BYTES, b"moo"[..]);
+    assert!(EMPTY.is_borrowed());
+    assert!(EMPTY.is_empty());
+}
+
+#[test]
+fn constant_c_str() {
+    assert!(C_STR.is_borrowed());
+    assert_eq!(C_STR, Cow::borrowed(MOO));
+    assert_eq!(C_STR.to_bytes(), b"moo");
+    assert_eq!(C_STR.clone().into_owned().as_bytes(), b"moo");
+}
+
+#[test]
+fn constant_const_item() {
+    const COW: Cow<str> = dairy::cow!("moo");
+    let c = COW;
+    assert!(c.is_borrowed());
+    assert_eq!(c, "moo");
+}
@@ -0,0 +1,148 @@
+use std::borrow::Borrow;
+use std::mem;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use dairy::repr::{Compact, Convert, Extent, Parts, Std};
+use dairy::{Cow, Dairy};
+
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+struct Name(str);
+
+#[derive(Debug, Clone, PartialEq)]
+struct NameBuf(String);
+
+impl Name {
+    fn new(s: &str) -> &Self {
+        unsafe { &*(s as *const str as *const Self) }
+    }
+}
+
+impl Borrow<Name> for NameBuf {
+    fn borrow(&self) -> &Name {
+        Name::new(&self.0)
+    }
+}
+
+impl ToOwned for Name {
+    type Owned = NameBuf;
+
+    fn to_owned(&self) -> NameBuf {
+        NameBuf(self.0.to_owned())
+    }
+}
+
+unsafe impl Convert for Name {
+    type Ptr = u8;
+    type Extent = Extent;
+
+    unsafe fn unmake_ptr(ptr: *const Self) -> Parts<Self> {
+        unsafe { str::unmake_ptr(ptr as *const str) }
+    }
+
+    fn try_unmake_owned(o: NameBuf) -> Result<Parts<Self>, NameBuf> {
+        str::try_unmake_owned(o.0).map_err(NameBuf)
+    }
+
+    #[cfg(target_has_atomic = "ptr")]
+    fn try_to_shared(o: NameBuf) -> Result<Arc<Self>, NameBuf> {
+        str::try_to_shared(o.0)
+            .map(|a| unsafe { Arc::from_raw(Arc::into_raw(a) as *const Self) })
+            .map_err(NameBuf)
+    }
+
+    unsafe fn make_ptr(ptr: NonNull<u8>, extent: Extent) -> *const Self {
+        unsafe { str::make_ptr(ptr, extent) as *const Self }
+    }
+
+    unsafe fn make_owned(ptr: NonNull<u8>, extent: Extent) -> NameBuf {
+        NameBuf(unsafe { str::make_owned(ptr, extent) })
+    }
+}
+
+impl Dairy for Name {
+    type Cow = Compact<Self>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    verbose: bool,
+}
+
+impl Dairy for Config {
+    type Cow = Std<Self>;
+}
+
+#[test]
+fn repr_compact_newtype() {
+    let c: Cow<Name> = Cow::borrowed(Name::new("Hello"));
+    assert!(c.is_borrowed());
+    assert_eq!(&c.0, "Hello");
+
+    let mut c: Cow<Name> = Cow::owned(NameBuf(String::from("Hello")));
+    assert!(c.is_owned());
+    c.to_mut().0.push_str(" World!");
+    assert_eq!(&c.0, "Hello World!");
+
+    let mut d = c.clone();
+    d.share();
+    assert!(d.is_shared());
+    assert_eq!(
+        d.clone().into_owned(),
+        NameBuf(String::from("Hello World!"))
+    );
+    assert_eq!(c.into_owned(), NameBuf(String::from("Hello World!")));
+
+    assert!(Cow::<Name>::owned(NameBuf(String::new())).is_owned());
+
+    assert_eq!(mem::size_of::<Cow<Name>>(), mem::size_of::<Cow<str>>());
+}
+
+#[test]
+fn repr_std_fallback() {
+    let config = Config { verbose: true };
+    let mut c: Cow<Config> = Cow::borrowed(&config);
+    assert!(c.is_borrowed());
+    c.to_mut().verbose = false;
+    assert!(c.is_owned());
+    assert_eq!(c.into_owned(), Config { verbose: false });
+
+    let c: Cow<Config> = Cow::owned(Config { verbose: true });
+    assert!(c.is_owned());
+    assert!(!c.is_borrowed());
+
+    assert_eq!(
+        mem::size_of::<Cow<Config>>(),
+        mem::size_of::<std::borrow::Cow<Config>>()
+    );
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn repr_extent_large_capacity() {
+    let ptr = NonNull::<u8>::dangling().as_ptr();
+    for &(len, cap) in &[
+        (1, 1 << 29),
+        (1, 3 << 40),
+        ((1 << 32) + 1, 1 << 33),
+        ((1 << 40) - 1, (1 << 40) + (1 << 30)),
+    ] {
+        assert!(Extent::can_pack(len, cap));
+        assert_eq!(Extent::fit_capacity(len, cap), cap);
+        let (_, extent) = unsafe { Extent::try_owned(ptr, len, cap) }.unwrap();
+        assert!(extent.is_owned());
+        assert!(!extent.is_shared());
+        assert_eq!(extent.len(), len);
+        assert_eq!(extent.capacity(), cap);
+    }
+
+    // The capacity is rounded down to the nearest one that can be stored
+    let cap = (1 << 29) + 1;
+    assert!(!Extent::can_pack(1, cap));
+    assert_eq!(Extent::fit_capacity(1, cap), 1 << 29);
+
+    // Unless that is smaller than the length
+    assert_eq!(Extent::fit_capacity(cap, cap), cap);
+    assert_eq!(Extent::fit_capacity(1 << 40, (1 << 41) + 1), 1 << 40);
+}
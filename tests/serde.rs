@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use std::path::Path;
+
+use dairy::Cow;
+use serde_derive::Deserialize;
+
+#[test]
+fn cow_str_deserialize_borrowed() {
+    let c: Cow<str> = serde_json::from_str(r#""Hello World!""#).unwrap();
+    assert!(c.is_borrowed());
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn cow_str_deserialize_owned() {
+    // Escapes require the string to be unescaped into a buffer
+    let c: Cow<str> = serde_json::from_str(r#""Hello\nWorld!""#).unwrap();
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello\nWorld!");
+}
+
+#[test]
+fn cow_slice_deserialize() {
+    let c: Cow<[u32]> = serde_json::from_str("[1, 2, 3]").unwrap();
+    assert!(c.is_owned());
+    assert_eq!(c, [1, 2, 3]);
+}
+
+#[test]
+fn cow_bytes_deserialize_borrowed() {
+    #[derive(Deserialize)]
+    struct Data<'a> {
+        #[serde(borrow, deserialize_with = "Cow::deserialize_bytes")]
+        bytes: Cow<'a, [u8]>,
+    }
+
+    let d: Data = serde_json::from_str(r#"{"bytes": "Hello World!"}"#).unwrap();
+    assert!(d.bytes.is_borrowed());
+    assert_eq!(d.bytes, b"Hello World!");
+}
+
+#[test]
+fn cow_path_deserialize() {
+    let c: Cow<Path> = serde_json::from_str(r#""/hello/world""#).unwrap();
+    assert!(c.is_owned());
+    assert_eq!(c, Path::new("/hello/world"));
+}
+
+#[test]
+fn cow_derive_deserialize_borrowed() {
+    #[derive(Deserialize)]
+    struct Config<'a> {
+        #[serde(borrow)]
+        name: dairy::String<'a>,
+        #[serde(borrow)]
+        names: Vec<dairy::String<'a>>,
+    }
+
+    let c: Config = serde_json::from_str(r#"{"name": "a", "names": ["b", "c"]}"#).unwrap();
+    assert!(c.name.is_borrowed());
+    assert!(c.names.iter().all(Cow::is_borrowed));
+}
+
+#[test]
+fn cow_serialize() {
+    let c: Cow<str> = Cow::borrowed("Hello World!");
+    assert_eq!(serde_json::to_string(&c).unwrap(), r#""Hello World!""#);
+
+    let c: Cow<[u32]> = Cow::owned(vec![1, 2, 3]);
+    assert_eq!(serde_json::to_string(&c).unwrap(), "[1,2,3]");
+}
+
+#[test]
+fn cow_sized_deserialize() {
+    let c: Cow<u32> = serde_json::from_str("42").unwrap();
+    assert!(c.is_owned());
+    assert_eq!(*c, 42);
+}
+
+#[test]
+fn cow_derive_deserialize_owned() {
+    #[derive(Deserialize)]
+    struct Config {
+        #[serde(deserialize_with = "Cow::deserialize_owned")]
+        name: Cow<'static, str>,
+    }
+
+    fn from_reader<T: serde::de::DeserializeOwned>(s: &str) -> T {
+        serde_json::from_reader(s.as_bytes()).unwrap()
+    }
+
+    let c: Config = from_reader(r#"{"name": "a"}"#);
+    assert!(c.name.is_owned());
+    assert_eq!(c.name, "a");
+}
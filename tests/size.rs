@@ -1,11 +1,12 @@
-use std::ffi::OsStr;
+use std::ffi::{CStr, OsStr};
 use std::mem;
+use std::path::Path;
 
 #[test]
 fn size_str() {
     const WORD: usize = mem::size_of::<usize>();
 
-    assert_eq!(mem::size_of::<std::borrow::Cow<str>>(), 4 * WORD);
+    assert_eq!(mem::size_of::<std::borrow::Cow<str>>(), 3 * WORD);
 
     #[cfg(target_pointer_width = "64")]
     assert_eq!(mem::size_of::<dairy::Cow<str>>(), 2 * WORD);
@@ -14,18 +15,136 @@ fn size_str() {
     assert_eq!(mem::size_of::<dairy::Cow<str>>(), 3 * WORD);
 }
 
+#[test]
+fn size_c_str() {
+    const WORD: usize = mem::size_of::<usize>();
+
+    assert_eq!(mem::size_of::<std::borrow::Cow<CStr>>(), 3 * WORD);
+
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(mem::size_of::<dairy::Cow<CStr>>(), 2 * WORD);
+
+    #[cfg(not(target_pointer_width = "64"))]
+    assert_eq!(mem::size_of::<dairy::Cow<CStr>>(), 3 * WORD);
+}
+
 #[test]
 fn size_os_str() {
     const WORD: usize = mem::size_of::<usize>();
 
-    assert_eq!(mem::size_of::<std::borrow::Cow<OsStr>>(), 4 * WORD);
+    assert_eq!(mem::size_of::<std::borrow::Cow<OsStr>>(), 3 * WORD);
 
-    #[cfg(all(target_pointer_width = "64", os_str_ext))]
+    #[cfg(target_pointer_width = "64")]
     assert_eq!(mem::size_of::<dairy::Cow<OsStr>>(), 2 * WORD);
 
-    #[cfg(all(not(target_pointer_width = "64"), os_str_ext))]
+    #[cfg(not(target_pointer_width = "64"))]
     assert_eq!(mem::size_of::<dairy::Cow<OsStr>>(), 3 * WORD);
+}
+
+#[test]
+fn size_path() {
+    const WORD: usize = mem::size_of::<usize>();
+
+    assert_eq!(mem::size_of::<std::borrow::Cow<Path>>(), 3 * WORD);
+
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(mem::size_of::<dairy::Cow<Path>>(), 2 * WORD);
+
+    #[cfg(not(target_pointer_width = "64"))]
+    assert_eq!(mem::size_of::<dairy::Cow<Path>>(), 3 * WORD);
+}
+
+#[test]
+fn size_small_cow() {
+    assert_eq!(
+        mem::size_of::<dairy::SmallCow>(),
+        mem::size_of::<dairy::Cow<str>>()
+    );
+}
+
+#[test]
+fn size_box_cow() {
+    const WORD: usize = mem::size_of::<usize>();
+
+    assert_eq!(mem::size_of::<dairy::BoxCow<str>>(), 2 * WORD);
+    assert_eq!(mem::size_of::<dairy::BoxCow<[u64]>>(), 2 * WORD);
+    assert_eq!(mem::size_of::<dairy::BoxCow<Path>>(), 2 * WORD);
+}
+
+#[test]
+fn size_sized() {
+    const WORD: usize = mem::size_of::<usize>();
+
+    assert_eq!(mem::size_of::<dairy::Cow<u64>>(), WORD);
+    assert_eq!(mem::size_of::<dairy::Cow<()>>(), WORD);
+    assert_eq!(mem::size_of::<dairy::Cow<String>>(), WORD);
+    assert_eq!(mem::size_of::<dairy::Cow<Vec<u8>>>(), WORD);
+
+    assert_eq!(
+        mem::size_of::<dairy::Cow<u8>>(),
+        mem::size_of::<std::borrow::Cow<u8>>()
+    );
+    assert_eq!(
+        mem::size_of::<dairy::Cow<[u16; 64]>>(),
+        mem::size_of::<std::borrow::Cow<[u16; 64]>>()
+    );
+}
+
+/// A small error type used to check that `Result` uses a niche.
+#[allow(dead_code)]
+enum SmallErr {
+    A,
+    B(u32),
+}
+
+fn assert_niches<T>() {
+    assert_eq!(mem::size_of::<Option<T>>(), mem::size_of::<T>());
+    assert_eq!(mem::size_of::<Option<Option<T>>>(), mem::size_of::<T>());
+    assert_eq!(mem::size_of::<Result<T, ()>>(), mem::size_of::<T>());
+    assert_eq!(mem::size_of::<Result<T, SmallErr>>(), mem::size_of::<T>());
+}
+
+#[test]
+fn size_niche() {
+    assert_niches::<dairy::Cow<str>>();
+    assert_niches::<dairy::Cow<[u8]>>();
+    assert_niches::<dairy::Cow<[String]>>();
+    assert_niches::<dairy::Cow<CStr>>();
+    assert_niches::<dairy::Cow<OsStr>>();
+    assert_niches::<dairy::Cow<Path>>();
+    assert_niches::<dairy::SmallCow>();
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        assert_niches::<dairy::BoxCow<str>>();
+        assert_niches::<dairy::BoxCow<[u8]>>();
+        assert_niches::<dairy::BoxCow<Path>>();
+    }
+}
+
+#[test]
+fn size_niche_single() {
+    assert_eq!(
+        mem::size_of::<Option<dairy::Cow<u64>>>(),
+        mem::size_of::<dairy::Cow<u64>>()
+    );
+    assert_eq!(
+        mem::size_of::<Option<dairy::Cow<String>>>(),
+        mem::size_of::<dairy::Cow<String>>()
+    );
+    assert_eq!(
+        mem::size_of::<Option<dairy::BoxCow<str>>>(),
+        mem::size_of::<dairy::BoxCow<str>>()
+    );
+    assert_eq!(
+        mem::size_of::<Option<dairy::BoxCow<[u8]>>>(),
+        mem::size_of::<dairy::BoxCow<[u8]>>()
+    );
+    assert_eq!(
+        mem::size_of::<Option<dairy::Cow<u8>>>(),
+        mem::size_of::<dairy::Cow<u8>>()
+    );
 
-    #[cfg(not(os_str_ext))]
-    assert_eq!(mem::size_of::<dairy::Cow<OsStr>>(), 4 * WORD);
+    // The tagged implementation only has a single niche
+    assert!(mem::size_of::<Option<Option<dairy::Cow<u64>>>>() > mem::size_of::<dairy::Cow<u64>>());
 }
@@ -0,0 +1,132 @@
+use dairy::repr::Tagged;
+use dairy::{Cow, Dairy};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    name: String,
+    verbose: bool,
+}
+
+impl Dairy for Config {
+    type Cow = Tagged<Self>;
+}
+
+fn config() -> Config {
+    Config {
+        name: String::from("moo"),
+        verbose: false,
+    }
+}
+
+#[test]
+fn cow_sized_is_borrowed() {
+    let config = config();
+    let c: Cow<Config> = Cow::borrowed(&config);
+    assert!(c.is_borrowed());
+    assert!(!c.is_owned());
+    assert_eq!(c.as_borrowed(), Some(&config));
+    assert_eq!(c.name, "moo");
+}
+
+#[test]
+fn cow_sized_is_owned() {
+    let c: Cow<Config> = Cow::owned(config());
+    assert!(!c.is_borrowed());
+    assert!(c.is_owned());
+    assert_eq!(c.into_owned(), config());
+}
+
+#[test]
+fn cow_sized_as_cow() {
+    let c: Cow<Config> = Cow::owned(config());
+    let r = c.as_cow();
+    assert!(r.is_borrowed());
+    assert!(std::ptr::eq(&*r, &*c));
+
+    let c: Cow<u8> = Cow::owned(7);
+    let r = c.as_cow();
+    assert!(r.is_borrowed());
+    assert_eq!(*r, 7);
+}
+
+#[test]
+fn cow_sized_to_mut() {
+    let config = config();
+    let mut c: Cow<Config> = Cow::borrowed(&config);
+    c.to_mut().verbose = true;
+    assert!(c.is_owned());
+    assert!(c.verbose);
+    assert!(!config.verbose);
+}
+
+#[test]
+fn cow_sized_eq() {
+    let config = config();
+    let a: Cow<Config> = Cow::borrowed(&config);
+    let b: Cow<Config> = Cow::owned(config.clone());
+    assert_eq!(a, b);
+    assert_eq!(a.clone(), b.clone());
+}
+
+#[test]
+fn cow_sized_align_one() {
+    let bytes = [1u8, 2, 3];
+    for b in &bytes {
+        let c: Cow<u8> = Cow::borrowed(b);
+        assert!(c.is_borrowed());
+        assert!(std::ptr::eq(c.as_borrowed().unwrap(), b));
+        assert_eq!(*c.clone(), *b);
+    }
+
+    let mut c: Cow<[u8; 3]> = Cow::borrowed(&bytes);
+    c.to_mut()[0] = 4;
+    assert!(c.is_owned());
+    assert_eq!(*c, [4, 2, 3]);
+    assert_eq!(c.into_owned(), [4, 2, 3]);
+}
+
+#[test]
+fn cow_sized_zero_sized() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Zst;
+
+    impl Dairy for Zst {
+        type Cow = Tagged<Self>;
+    }
+
+    impl Drop for Zst {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let zst = Zst;
+    let c: Cow<Zst> = Cow::borrowed(&zst);
+    assert!(c.is_borrowed());
+    drop(c);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+    let c: Cow<Zst> = Cow::owned(Zst);
+    assert!(c.is_owned());
+    let d = c.clone();
+    drop(c);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    let _ = d.into_owned();
+    assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn cow_sized_into_inner() {
+    let config = config();
+    let c: Cow<Config> = Cow::owned(config.clone());
+    let c = std::borrow::Cow::from(c);
+    assert!(matches!(c, std::borrow::Cow::Owned(_)));
+
+    let c: Cow<Config> = Cow::borrowed(&config);
+    let c = std::borrow::Cow::from(c);
+    assert!(matches!(c, std::borrow::Cow::Borrowed(_)));
+}
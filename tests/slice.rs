@@ -4,7 +4,7 @@ use std::borrow::Borrow;
 use std::ffi::OsStr;
 use std::iter::FromIterator;
 
-use dairy::Cow;
+use dairy::{Cow, Ownership};
 
 type T<'a> = Cow<'a, [&'a str]>;
 
@@ -22,6 +22,37 @@ fn cow_slice_is_owned() {
     assert!(c.is_owned());
 }
 
+#[test]
+fn cow_slice_is_owned_empty() {
+    let c = T::owned(Vec::new());
+    assert!(c.is_owned());
+    assert!(!c.is_borrowed());
+
+    let c = T::owned(Vec::with_capacity(10));
+    assert!(c.is_owned());
+    assert!(c.clone().is_owned());
+    assert!(T::default().is_owned());
+
+    let c: Cow<[()]> = Cow::owned(Vec::new());
+    assert!(c.is_owned());
+
+    let c = T::borrowed(&[]);
+    assert!(c.is_borrowed());
+    assert!(!c.is_owned());
+}
+
+#[test]
+fn cow_slice_is_owned_empty_to_mut() {
+    let mut c = T::borrowed(&[]);
+    c.to_mut();
+    assert!(c.is_owned());
+
+    let mut c = T::owned(vec!["Hello"]);
+    c.apply(Vec::clear);
+    assert!(c.is_owned());
+    assert!(matches!(c.into_inner(), Ownership::Owned(v) if v.is_empty()));
+}
+
 #[test]
 fn cow_slice_borrowed_into_owned() {
     let c = T::borrowed(&["Hello", "World!"]);
@@ -62,6 +93,28 @@ fn cow_slice_owned_ref() {
     assert_eq!(v, &["Hello", "World!"]);
 }
 
+#[test]
+fn cow_slice_as_borrowed() {
+    let v = vec!["Hello", "World!"];
+    let b: &[&str] = T::borrowed(&v).as_borrowed().unwrap();
+    assert_eq!(b, &["Hello", "World!"]);
+
+    let c = T::owned(vec!["Hello", "World!"]);
+    assert_eq!(c.as_borrowed(), None);
+}
+
+#[test]
+fn cow_slice_into_inner() {
+    let c = T::borrowed(&["Hello", "World!"]);
+    assert!(matches!(
+        c.into_inner(),
+        Ownership::Borrowed(&["Hello", "World!"])
+    ));
+
+    let c = T::owned(vec!["Hello", "World!"]);
+    assert!(matches!(c.into_inner(), Ownership::Owned(v) if v == ["Hello", "World!"]));
+}
+
 #[test]
 fn cow_slice_borrowed_clone() {
     let c1 = T::borrowed(&["Hello", "World!"]);
@@ -78,6 +131,18 @@ fn cow_slice_owned_clone() {
     assert!(c2.is_owned());
 }
 
+#[test]
+fn cow_slice_share() {
+    let mut c = T::owned(vec!["Hello", "World!"]);
+    c.share();
+    assert!(c.is_shared());
+    let c2 = c.clone();
+    assert_eq!(c2.as_ptr(), c.as_ptr());
+    drop(c);
+    assert_eq!(c2, &["Hello", "World!"]);
+    assert_eq!(c2.into_owned(), vec!["Hello", "World!"]);
+}
+
 #[test]
 fn cow_slice_borrowed_as_ref() {
     let c = T::borrowed(&["Hello", "World!"]);
@@ -108,6 +173,42 @@ fn cow_slice_from() {
     assert!(Cow::<[&str]>::from(vec!["Hello", "World!"].into_boxed_slice()).is_owned());
 }
 
+#[test]
+fn cow_slice_std() {
+    let c = std::borrow::Cow::from(T::borrowed(&["Hello", "World!"]));
+    assert!(matches!(
+        c,
+        std::borrow::Cow::Borrowed(&["Hello", "World!"])
+    ));
+    assert!(T::from(c).is_borrowed());
+
+    let c = std::borrow::Cow::from(T::owned(vec!["Hello", "World!"]));
+    assert!(matches!(c, std::borrow::Cow::Owned(ref o) if o == &["Hello", "World!"]));
+    assert!(T::from(c).is_owned());
+}
+
+#[test]
+fn cow_slice_slice() {
+    let v = ["Hello", "World", "!"];
+    let c = T::borrowed(&v).slice(1..);
+    assert!(c.is_borrowed());
+    assert_eq!(c, &["World", "!"]);
+    assert_eq!(c.as_ptr(), v[1..].as_ptr());
+
+    let o = vec!["Hello", "World", "!"];
+    let ptr = o.as_ptr();
+    let c = T::owned(o).slice(..2);
+    assert!(c.is_owned());
+    assert_eq!(c, &["Hello", "World"]);
+    assert_eq!(c.as_ptr(), ptr);
+}
+
+#[test]
+#[should_panic]
+fn cow_slice_slice_out_of_bounds() {
+    let _ = T::borrowed(&["Hello", "World!"]).slice(1..3);
+}
+
 #[test]
 fn cow_slice_borrowed_partial_eq() {
     let c = T::borrowed(&["Hello", "World!"]);
@@ -144,3 +245,163 @@ fn cow_slice_from_iter() {
     let c = T::from_iter(&["Hello", "World!"]);
     assert_eq!(c, &["Hello", "World!"]);
 }
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn cow_slice_borrowed_large() {
+    let len = 1 << 33;
+    let v: &[()] =
+        unsafe { std::slice::from_raw_parts(std::ptr::NonNull::dangling().as_ptr(), len) };
+    let c = Cow::borrowed(v);
+    assert!(c.is_borrowed());
+    assert_eq!(c.len(), len);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[ignore = "allocates more than 4 GiB"]
+fn cow_slice_owned_large() {
+    let len = (1 << 32) + 1;
+    let c: Cow<[u8]> = Cow::owned(vec![0; len]);
+    assert!(c.is_owned());
+    assert_eq!(c.len(), len);
+    assert_eq!(c.into_owned().capacity(), len);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[cfg_attr(miri, ignore)]
+fn cow_slice_owned_large_capacity() {
+    // The allocation is never touched so this does not use the memory
+    let mut v: Vec<u8> = Vec::with_capacity(1 << 30);
+    v.push(1);
+    let mut c: Cow<[u8]> = Cow::owned(v);
+    c.apply(|v| v.push(2));
+    assert_eq!(c, [1, 2]);
+    assert_eq!(c.into_owned().capacity(), 1 << 30);
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[allow(clippy::uninit_vec)]
+fn cow_slice_try_owned_capacity_error() {
+    let mut v: Vec<()> = Vec::new();
+    unsafe { v.set_len(usize::MAX) };
+    let err = Cow::<[()]>::try_owned(v).unwrap_err();
+    assert_eq!(err.to_string(), "capacity out of bounds");
+    assert_eq!(err.into_inner().len(), usize::MAX);
+
+    let mut c: Cow<[()]> = Cow::borrowed(&[(), ()]);
+    let err = c
+        .checked_apply(|v| unsafe { v.set_len(usize::MAX) })
+        .unwrap_err();
+    assert_eq!(err.into_inner().len(), usize::MAX);
+    assert!(c.is_empty());
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[allow(clippy::uninit_vec)]
+fn cow_slice_to_mut_capacity_error() {
+    let mut c: Cow<[()]> = Cow::borrowed(&[(), ()]);
+    unsafe { c.to_mut().set_len(usize::MAX) };
+    assert!(c.is_empty());
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[should_panic(expected = "capacity out of bounds")]
+#[allow(clippy::uninit_vec)]
+fn cow_slice_apply_with_capacity_error() {
+    let mut c: Cow<[()]> = Cow::borrowed(&[(), ()]);
+    c.apply_with(|v| unsafe { v.set_len(usize::MAX) });
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[should_panic(expected = "capacity out of bounds")]
+#[allow(clippy::uninit_vec)]
+fn cow_slice_try_apply_capacity_error() {
+    let mut c: Cow<[()]> = Cow::borrowed(&[(), ()]);
+    let _ = c.try_apply(|v| {
+        unsafe { v.set_len(usize::MAX) };
+        Ok::<(), ()>(())
+    });
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Marker;
+
+#[test]
+fn cow_slice_zst_owned() {
+    let c: Cow<[()]> = Cow::owned(vec![(); 3]);
+    assert!(c.is_owned());
+    assert_eq!(c.len(), 3);
+    assert_eq!(c, &[(), (), ()]);
+
+    let v = c.into_owned();
+    assert_eq!(v.len(), 3);
+    assert_eq!(v.capacity(), usize::MAX);
+}
+
+#[test]
+fn cow_slice_zst_borrowed() {
+    let c: Cow<[Marker]> = Cow::borrowed(&[Marker, Marker]);
+    assert!(c.is_borrowed());
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.into_owned(), vec![Marker, Marker]);
+}
+
+#[test]
+fn cow_slice_zst_apply() {
+    let mut c: Cow<[Marker]> = Cow::borrowed(&[Marker]);
+    c.apply(|v| v.push(Marker));
+    assert!(c.is_owned());
+    assert_eq!(c.len(), 2);
+
+    c.to_mut().truncate(1);
+    c.apply(|v| v.extend(vec![Marker; 4]));
+    assert_eq!(c.len(), 5);
+    assert_eq!(c.into_owned(), vec![Marker; 5]);
+}
+
+#[test]
+fn cow_slice_zst_clone() {
+    let c: Cow<[()]> = Cow::owned(vec![(); 1 << 20]);
+    let d = c.clone();
+    assert!(d.is_owned());
+    assert_eq!(d.len(), 1 << 20);
+    assert_eq!(c, d);
+
+    let mut s = d.clone();
+    s.share();
+    assert!(s.is_shared());
+    assert_eq!(s.clone().len(), 1 << 20);
+    assert_eq!(s.into_owned().len(), 1 << 20);
+}
+
+#[test]
+fn cow_slice_zst_into_owned() {
+    let c: Cow<[Marker]> = Cow::owned(Vec::with_capacity(10));
+    assert!(c.is_empty());
+    let mut v = c.into_owned();
+    v.push(Marker);
+    assert_eq!(v, [Marker]);
+
+    let c: Cow<[Marker]> = Cow::try_owned(vec![Marker; 7]).unwrap();
+    let c = std::borrow::Cow::from(c);
+    assert!(matches!(c, std::borrow::Cow::Owned(ref o) if o.len() == 7));
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+#[allow(clippy::uninit_vec)]
+fn cow_slice_zst_owned_large() {
+    let len = (1 << 32) + 1;
+    let mut v: Vec<()> = Vec::new();
+    unsafe { v.set_len(len) };
+    let c: Cow<[()]> = Cow::owned(v);
+    assert!(c.is_owned());
+    assert_eq!(c.len(), len);
+    assert_eq!(c.into_owned().len(), len);
+}
@@ -0,0 +1,113 @@
+use dairy::{Cow, SmallCow};
+
+#[test]
+fn small_cow_is_borrowed() {
+    let c = SmallCow::borrowed("Hello");
+    assert!(c.is_borrowed());
+    assert!(!c.is_owned());
+    assert!(!c.is_inline());
+    assert_eq!(c, "Hello");
+}
+
+#[test]
+fn small_cow_is_owned_inline() {
+    let s = "a".repeat(SmallCow::INLINE_CAPACITY);
+    let c = SmallCow::owned(s.clone());
+    assert!(c.is_owned());
+    assert!(c.is_inline());
+    assert_eq!(c, s);
+    assert_eq!(c.len(), SmallCow::INLINE_CAPACITY);
+
+    let c = SmallCow::owned(String::new());
+    assert!(c.is_owned());
+    assert!(c.is_inline());
+    assert_eq!(c, "");
+}
+
+#[test]
+fn small_cow_is_owned_heap() {
+    let s = "a".repeat(SmallCow::INLINE_CAPACITY + 1);
+    let ptr = s.as_ptr();
+    let c = SmallCow::owned(s);
+    assert!(c.is_owned());
+    assert!(!c.is_inline());
+    assert_eq!(c.len(), SmallCow::INLINE_CAPACITY + 1);
+    let s = c.into_owned();
+    assert_eq!(s.as_ptr(), ptr);
+}
+
+#[test]
+fn small_cow_copied() {
+    let c = SmallCow::copied("Hello");
+    assert!(c.is_inline());
+    assert_eq!(c, "Hello");
+
+    let c: SmallCow = "Hello World! Hello World!".parse().unwrap();
+    assert!(c.is_owned());
+    assert!(!c.is_inline());
+    assert_eq!(c, "Hello World! Hello World!");
+}
+
+#[test]
+fn small_cow_as_borrowed() {
+    let s = String::from("Hello World!");
+    let b: &str = SmallCow::borrowed(&s).as_borrowed().unwrap();
+    assert_eq!(b, "Hello World!");
+    assert_eq!(SmallCow::owned(String::from("Hello")).as_borrowed(), None);
+}
+
+#[test]
+fn small_cow_clone() {
+    let c1 = SmallCow::owned(String::from("Hello"));
+    let c2 = c1.clone();
+    assert!(c2.is_inline());
+    assert_eq!(c1, c2);
+
+    let c1 = SmallCow::owned(String::from("Hello World! Hello World!"));
+    let c2 = c1.clone();
+    assert!(c2.is_owned());
+    assert_eq!(c1, c2);
+}
+
+#[test]
+fn small_cow_apply() {
+    // Fits inline on every platform
+    let mut c = SmallCow::borrowed("Hello");
+    c.apply(|s| s.push_str(" World"));
+    assert!(c.is_inline());
+    assert_eq!(c, "Hello World");
+
+    c.apply(|s| s.push_str("! Hello World!"));
+    assert!(!c.is_inline());
+    assert_eq!(c, "Hello World! Hello World!");
+
+    c.apply(|s| s.truncate(5));
+    assert_eq!(c, "Hello");
+}
+
+#[test]
+fn small_cow_to_mut() {
+    let mut c = SmallCow::owned(String::from("Hello"));
+    c.to_mut().push_str(" World!");
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn small_cow_from() {
+    assert!(SmallCow::from("Hello").is_borrowed());
+    assert!(SmallCow::from(String::from("Hello")).is_inline());
+    assert!(SmallCow::from('H').is_inline());
+    assert_eq!(SmallCow::from('é'), "é");
+
+    assert!(SmallCow::from(Cow::borrowed("Hello")).is_borrowed());
+    assert!(SmallCow::from(Cow::owned(String::from("Hello"))).is_inline());
+    assert!(Cow::from(SmallCow::borrowed("Hello")).is_borrowed());
+    assert!(Cow::from(SmallCow::owned(String::from("Hello"))).is_owned());
+
+    assert!(matches!(
+        std::borrow::Cow::from(SmallCow::owned(String::from("Hello"))),
+        std::borrow::Cow::Owned(s) if s == "Hello"
+    ));
+    assert_eq!(String::from(SmallCow::from("Hello")), "Hello");
+}
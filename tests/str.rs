@@ -5,7 +5,7 @@ use std::ffi::{OsStr, OsString};
 use std::iter::FromIterator;
 use std::path::Path;
 
-use dairy::Cow;
+use dairy::{Cow, Ownership};
 
 type T<'a> = Cow<'a, str>;
 
@@ -23,6 +23,73 @@ fn cow_str_is_owned() {
     assert!(c.is_owned());
 }
 
+#[test]
+fn cow_str_is_owned_empty() {
+    let c = T::owned(String::new());
+    let std = std::borrow::Cow::<str>::Owned(String::new());
+    assert!(c.is_owned());
+    assert!(!c.is_borrowed());
+    assert_eq!(c.is_owned(), matches!(std, std::borrow::Cow::Owned(_)));
+
+    let c = T::owned(String::with_capacity(10));
+    assert!(c.is_owned());
+    assert!(c.clone().is_owned());
+    assert!(T::default().is_owned());
+
+    let c = T::borrowed("");
+    assert!(c.is_borrowed());
+    assert!(!c.is_owned());
+}
+
+#[test]
+fn cow_str_is_owned_empty_to_mut() {
+    let mut c = T::borrowed("");
+    c.to_mut();
+    assert!(c.is_owned());
+
+    let mut c = T::owned(String::from("moo"));
+    c.apply(String::clear);
+    assert!(c.is_owned());
+    assert!(matches!(c.into_inner(), Ownership::Owned(s) if s.is_empty()));
+}
+
+#[test]
+fn cow_str_as_cow() {
+    let c = T::owned(String::from("Hello World!"));
+    let r = c.as_cow();
+    assert!(r.is_borrowed());
+    assert_eq!(r, "Hello World!");
+    assert_eq!(r.as_ptr(), c.as_ptr());
+    assert!(c.is_owned());
+
+    let c = T::borrowed("Hello World!");
+    let r = c.as_cow();
+    assert!(r.is_borrowed());
+    assert_eq!(r.as_ptr(), c.as_ptr());
+
+    let mut c = T::owned(String::from("Hello World!"));
+    c.share();
+    let r = c.as_cow();
+    assert!(r.is_borrowed());
+    assert_eq!(r.as_ptr(), c.as_ptr());
+}
+
+#[test]
+fn cow_str_as_cow_to_mut() {
+    let c = T::owned(String::from("Hello"));
+    let mut r = c.as_cow();
+    r.to_mut().push_str(" World!");
+    assert_eq!(r, "Hello World!");
+    assert_eq!(c, "Hello");
+}
+
+#[test]
+fn cow_str_try_owned() {
+    let c = T::try_owned(String::from("Hello World!")).unwrap();
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World!");
+}
+
 #[test]
 fn cow_str_borrowed_into_owned() {
     let c = T::borrowed("Hello World!");
@@ -63,6 +130,28 @@ fn cow_str_owned_ref() {
     assert_eq!(s, "Hello World!");
 }
 
+#[test]
+fn cow_str_as_borrowed() {
+    let s = String::from("Hello World!");
+    let b: &str = T::borrowed(&s).as_borrowed().unwrap();
+    assert_eq!(b, "Hello World!");
+
+    let c = T::owned(String::from("Hello World!"));
+    assert_eq!(c.as_borrowed(), None);
+}
+
+#[test]
+fn cow_str_into_inner() {
+    let c = T::borrowed("Hello World!");
+    assert!(matches!(
+        c.into_inner(),
+        Ownership::Borrowed("Hello World!")
+    ));
+
+    let c = T::owned(String::from("Hello World!"));
+    assert!(matches!(c.into_inner(), Ownership::Owned(s) if s == "Hello World!"));
+}
+
 #[test]
 fn cow_str_borrowed_clone() {
     let c1 = T::borrowed("Hello World!");
@@ -125,6 +214,245 @@ fn cow_str_from() {
     assert!(T::from(String::from("Hello World!").into_boxed_str()).is_owned());
 }
 
+#[test]
+fn cow_str_std() {
+    let s = "Hello World!";
+    let c = std::borrow::Cow::from(T::borrowed(s));
+    assert!(matches!(c, std::borrow::Cow::Borrowed(b) if b.as_ptr() == s.as_ptr()));
+    assert!(T::from(c).is_borrowed());
+
+    let s = String::from("Hello World!");
+    let ptr = s.as_ptr();
+    let c = std::borrow::Cow::from(T::owned(s));
+    assert!(matches!(c, std::borrow::Cow::Owned(ref o) if o.as_ptr() == ptr));
+    assert!(T::from(c).is_owned());
+}
+
+#[test]
+fn cow_str_share() {
+    let mut c = T::owned(String::from("Hello World!"));
+    c.share();
+    assert!(c.is_shared());
+    assert!(!c.is_owned());
+    assert!(!c.is_borrowed());
+    assert_eq!(c.as_borrowed(), None);
+    assert_eq!(c, "Hello World!");
+
+    let c2 = c.clone();
+    assert!(c2.is_shared());
+    assert_eq!(c2.as_ptr(), c.as_ptr());
+    drop(c);
+    assert_eq!(c2, "Hello World!");
+
+    let c3 = c2.clone();
+    assert!(matches!(c3.into_inner(), Ownership::Owned(s) if s == "Hello World!"));
+    assert_eq!(c2.into_owned(), "Hello World!");
+}
+
+#[test]
+fn cow_str_share_borrowed() {
+    let mut c = T::borrowed("Hello World!");
+    c.share();
+    assert!(c.is_borrowed());
+}
+
+#[test]
+fn cow_str_share_apply() {
+    let mut c = T::owned(String::from("Hello"));
+    c.share();
+    let c2 = c.clone();
+    c.apply(|s| s.push_str(" World!"));
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World!");
+    assert!(c2.is_shared());
+    assert_eq!(c2, "Hello");
+
+    let mut c = c2.clone();
+    c.to_mut().push('!');
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello!");
+    assert_eq!(c2, "Hello");
+}
+
+#[test]
+fn cow_str_to_mut() {
+    let mut c = T::borrowed("Hello");
+    c.to_mut().push_str(" World");
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World");
+
+    let mut s = c.to_mut();
+    s.push('!');
+    drop(s);
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn cow_str_to_mut_early_return() {
+    fn push(c: &mut T, s: &str) -> Result<(), ()> {
+        let mut o = c.to_mut();
+        if s.is_empty() {
+            return Err(());
+        }
+        o.push_str(s);
+        Ok(())
+    }
+
+    let mut c = T::borrowed("Hello");
+    assert!(push(&mut c, " World!").is_ok());
+    assert!(push(&mut c, "").is_err());
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn cow_str_to_mut_panic() {
+    let mut c = T::owned(String::from("Hello"));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut s = c.to_mut();
+        s.push_str(" World!");
+        panic!("oops");
+    }));
+    assert!(result.is_err());
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn cow_str_apply() {
+    let mut c = T::borrowed("Hello");
+    c.apply(|s| s.push_str(" World!"));
+    assert_eq!(c, "Hello World!");
+
+    let n = c.apply_with(|s| {
+        s.truncate(5);
+        s.len()
+    });
+    assert_eq!(n, 5);
+    assert_eq!(c, "Hello");
+}
+
+#[test]
+fn cow_str_try_apply() {
+    let mut c = T::borrowed("Hello");
+    let r: Result<usize, ()> = c.try_apply(|s| {
+        s.push_str(" World!");
+        Ok(s.len())
+    });
+    assert_eq!(r, Ok(12));
+    assert_eq!(c, "Hello World!");
+
+    let r: Result<(), &str> = c.try_apply(|s| {
+        s.clear();
+        Err("oops")
+    });
+    assert_eq!(r, Err("oops"));
+    assert_eq!(c, "");
+}
+
+#[test]
+fn cow_str_modify_if() {
+    let mut c = T::borrowed("Hello");
+    c.modify_if(|s| s.is_empty(), |s| s.push_str("World!"));
+    assert!(c.is_borrowed());
+    assert_eq!(c, "Hello");
+
+    c.modify_if(|s| !s.is_empty(), |s| s.push_str(" World!"));
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World!");
+}
+
+#[test]
+fn cow_str_map_borrowed() {
+    let c = T::borrowed("  Hello World!  ").map_borrowed(|s| T::borrowed(s.trim()));
+    assert!(c.is_borrowed());
+    assert_eq!(c, "Hello World!");
+
+    let c = T::owned(String::from("  Hello World!  ")).map_borrowed(|s| T::borrowed(s.trim()));
+    assert!(c.is_owned());
+    assert_eq!(c, "  Hello World!  ");
+}
+
+#[test]
+fn cow_str_trim_cow() {
+    let s = "  Hello World!  ";
+    let c = T::borrowed(s).trim_cow();
+    assert!(c.is_borrowed());
+    assert_eq!(c, "Hello World!");
+    assert_eq!(c.as_ptr(), s[2..].as_ptr());
+
+    assert_eq!(T::borrowed(s).trim_start_cow(), "Hello World!  ");
+    assert_eq!(T::borrowed(s).trim_end_cow(), "  Hello World!");
+
+    let o = String::from(s);
+    let ptr = o.as_ptr();
+    let c = T::owned(o).trim_cow();
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello World!");
+    assert_eq!(c.as_ptr(), ptr);
+}
+
+#[test]
+fn cow_str_replace_cow() {
+    let c = T::borrowed("Hello World!").replace_cow("Moo", "Baa");
+    assert!(c.is_borrowed());
+
+    let c = T::borrowed("Hello World!").replace_cow("World", "Moo");
+    assert!(c.is_owned());
+    assert_eq!(c, "Hello Moo!");
+}
+
+#[test]
+fn cow_str_case_cow() {
+    assert!(T::borrowed("hello world!").to_lowercase_cow().is_borrowed());
+    assert_eq!(T::borrowed("Hello ΣΑ!").to_lowercase_cow(), "hello σα!");
+
+    assert!(T::borrowed("HELLO WORLD!").to_uppercase_cow().is_borrowed());
+    assert_eq!(
+        T::borrowed("Hello World!").to_uppercase_cow(),
+        "HELLO WORLD!"
+    );
+
+    assert!(T::borrowed("hello wörld!")
+        .to_ascii_lowercase_cow()
+        .is_borrowed());
+    assert_eq!(
+        T::borrowed("Hello Wörld!").to_ascii_lowercase_cow(),
+        "hello wörld!"
+    );
+
+    assert!(T::borrowed("HELLO WöRLD!")
+        .to_ascii_uppercase_cow()
+        .is_borrowed());
+    assert_eq!(
+        T::borrowed("Hello Wörld!").to_ascii_uppercase_cow(),
+        "HELLO WöRLD!"
+    );
+}
+
+#[test]
+fn cow_str_slice() {
+    let s = "Hello World!";
+    let c = T::borrowed(s).slice(6..11);
+    assert!(c.is_borrowed());
+    assert_eq!(c, "World");
+    assert_eq!(c.as_ptr(), s[6..].as_ptr());
+    assert_eq!(T::borrowed(s).slice(..5), "Hello");
+    assert_eq!(T::borrowed(s).slice(6..=10), "World");
+    assert_eq!(T::borrowed(s).slice(..), "Hello World!");
+
+    let o = String::from(s);
+    let ptr = o.as_ptr();
+    let c = T::owned(o).slice(6..);
+    assert!(c.is_owned());
+    assert_eq!(c, "World!");
+    assert_eq!(c.as_ptr(), ptr);
+}
+
+#[test]
+#[should_panic]
+fn cow_str_slice_char_boundary() {
+    let _ = T::owned(String::from("Hellö")).slice(..5);
+}
+
 #[test]
 fn cow_str_borrowed_partial_eq() {
     let c = T::borrowed("Hello World!");
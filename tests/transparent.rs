@@ -0,0 +1,123 @@
+use std::mem;
+
+use dairy::{BoxCow, Cow};
+
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Name(str);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameBuf(String);
+
+#[derive(Debug, PartialEq)]
+#[repr(transparent)]
+pub struct Bytes([u8]);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ByteBuf(Vec<u8>);
+
+fn validate_name(s: &str) -> Result<(), &'static str> {
+    if s.is_empty() {
+        Err("name must not be empty")
+    } else {
+        Ok(())
+    }
+}
+
+// SAFETY: Both types are transparent wrappers.
+dairy::transparent_cow! {
+    unsafe Name(str) => NameBuf(String) where validate = validate_name,
+    unsafe Bytes([u8]) => ByteBuf(Vec<u8>),
+}
+
+fn name(s: &str) -> &Name {
+    unsafe { &*(s as *const str as *const Name) }
+}
+
+#[test]
+fn transparent_cow_str() {
+    let c: Cow<Name> = Cow::from(name("Hello"));
+    assert!(c.is_borrowed());
+    assert_eq!(c, name("Hello"));
+
+    let mut c: Cow<Name> = Cow::from(NameBuf(String::from("Hello")));
+    assert!(c.is_owned());
+    c.to_mut().0.push_str(" World!");
+    assert_eq!(c, NameBuf(String::from("Hello World!")));
+    assert_eq!(NameBuf::from(c), NameBuf(String::from("Hello World!")));
+
+    assert_eq!(mem::size_of::<Cow<Name>>(), mem::size_of::<Cow<str>>());
+}
+
+#[test]
+fn transparent_cow_slice() {
+    let v = ByteBuf(vec![1, 2, 3]);
+    let c: Cow<Bytes> = Cow::from(&v);
+    assert!(c.is_borrowed());
+    assert_eq!(c, v);
+
+    let c = c.into_owned();
+    assert_eq!(c, v);
+}
+
+#[test]
+fn transparent_cow_boxed() {
+    let b: Box<Name> = Cow::from(NameBuf(String::from("Hello"))).into_boxed();
+    let ptr = b.0.as_ptr();
+    let c: Cow<Name> = Cow::from(b);
+    assert!(c.is_owned());
+    assert_eq!(c.0.as_ptr(), ptr);
+
+    let c: BoxCow<Name> = BoxCow::from(c);
+    assert!(c.is_owned());
+    assert_eq!(&c.0, "Hello");
+}
+
+#[test]
+fn transparent_cow_share() {
+    let mut c: Cow<Name> = Cow::from(NameBuf(String::from("Hello")));
+    c.share();
+    assert!(c.is_shared());
+    let d = c.clone();
+    assert_eq!(c.0.as_ptr(), d.0.as_ptr());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn transparent_cow_deserialize() {
+    let c: Cow<Name> = serde_json::from_str(r#""Hello World!""#).unwrap();
+    assert!(c.is_borrowed());
+    assert_eq!(c, name("Hello World!"));
+
+    let c: Cow<Name> = serde_json::from_str(r#""Hello\nWorld!""#).unwrap();
+    assert!(c.is_owned());
+    assert_eq!(c, name("Hello\nWorld!"));
+
+    let err = serde_json::from_str::<Cow<Name>>(r#""""#).unwrap_err();
+    assert!(err.to_string().starts_with("name must not be empty"));
+}
+
+#[allow(dead_code)]
+mod shadowed {
+    // The macro must not pick up these items instead of the prelude.
+    struct AsRef;
+    struct From;
+    struct PartialEq;
+    struct Result;
+    struct Option;
+    struct Box;
+
+    #[repr(transparent)]
+    pub struct Name(pub str);
+
+    pub struct NameBuf(pub String);
+
+    // SAFETY: `Name` is a transparent wrapper around `str`.
+    dairy::transparent_cow!(unsafe Name(str) => NameBuf(String));
+}
+
+#[test]
+fn transparent_cow_shadowed_prelude() {
+    let c: Cow<shadowed::Name> = Cow::from(shadowed::NameBuf(String::from("Hello")));
+    assert_eq!(&c.0, "Hello");
+}
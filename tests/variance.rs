@@ -0,0 +1,74 @@
+use std::ffi::{CStr, OsStr};
+use std::path::Path;
+
+use dairy::{BoxCow, Cow, SmallCow};
+
+fn shrink<'a>(c: Cow<'static, str>) -> Cow<'a, str> {
+    c
+}
+
+fn shrink_slice<'a>(c: Cow<'static, [u8]>) -> Cow<'a, [u8]> {
+    c
+}
+
+fn shrink_c_str<'a>(c: Cow<'static, CStr>) -> Cow<'a, CStr> {
+    c
+}
+
+fn shrink_os_str<'a>(c: Cow<'static, OsStr>) -> Cow<'a, OsStr> {
+    c
+}
+
+fn shrink_path<'a>(c: Cow<'static, Path>) -> Cow<'a, Path> {
+    c
+}
+
+fn shrink_sized<'a>(c: Cow<'static, u64>) -> Cow<'a, u64> {
+    c
+}
+
+fn shrink_box_cow<'a>(c: BoxCow<'static, str>) -> BoxCow<'a, str> {
+    c
+}
+
+fn shrink_small_cow<'a>(c: SmallCow<'static>) -> SmallCow<'a> {
+    c
+}
+
+struct Config<'a> {
+    name: Cow<'a, str>,
+    tags: Vec<Cow<'a, str>>,
+}
+
+fn shrink_struct<'a>(c: Config<'static>) -> Config<'a> {
+    c
+}
+
+#[test]
+fn variance_covariant() {
+    assert_eq!(shrink(Cow::borrowed("moo")), "moo");
+    assert_eq!(shrink_slice(Cow::borrowed(b"moo")), b"moo"[..]);
+    let c_str = CStr::from_bytes_with_nul(b"moo\0").unwrap();
+    assert_eq!(shrink_c_str(Cow::borrowed(c_str)), c_str);
+    assert_eq!(
+        shrink_os_str(Cow::borrowed(OsStr::new("moo"))),
+        OsStr::new("moo")
+    );
+    assert_eq!(shrink_path(Cow::from("moo")), Path::new("moo"));
+    assert_eq!(*shrink_sized(Cow::owned(7)), 7);
+    assert_eq!(shrink_box_cow(BoxCow::borrowed("moo")), "moo");
+    assert_eq!(shrink_small_cow(SmallCow::borrowed("moo")), "moo");
+}
+
+#[test]
+fn variance_struct() {
+    let local = String::from("local");
+    let config = shrink_struct(Config {
+        name: Cow::borrowed("static"),
+        tags: vec![Cow::borrowed("a")],
+    });
+    let mut tags = config.tags;
+    tags.push(Cow::borrowed(&local));
+    assert_eq!(config.name, "static");
+    assert_eq!(tags, ["a", "local"]);
+}